@@ -7,6 +7,7 @@ extern crate bencher;
 use bencher::{black_box, Bencher};
 
 use parse_that::json::json_parser;
+use parse_that::{Parser, recursive, regex, sp_regex, string};
 
 fn data_dir() -> std::path::PathBuf {
     Path::new(env!("CARGO_MANIFEST_DIR")).join("../../data/json")
@@ -52,4 +53,117 @@ fn parse(b: &mut Bencher, filepath: &str) {
 
 benchmark_group!(json, data, canada, apache, data_xl, twitter, citm_catalog);
 
-benchmark_main!(json);
+// ── map: fn item vs capturing closure ─────────────────────────
+
+fn numbers() -> String {
+    (0..50_000).map(|i| i.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn to_int(s: &str) -> u64 {
+    s.parse().unwrap()
+}
+
+fn map_fn(b: &mut Bencher) {
+    let data = numbers();
+    b.bytes = data.len() as u64;
+    let parser = regex(r"\d+").map(to_int).sep_by(string(","), ..);
+    b.iter(|| parser.parse(black_box(&data)).unwrap().len())
+}
+
+/// The shape `map` had when it took `fn(Output) -> O2`: an indirect call.
+/// Measured next to `map_fn` in the same run, so machine noise between runs
+/// does not hide a regression in the fn item path.
+fn map_fn_pointer(b: &mut Bencher) {
+    let data = numbers();
+    b.bytes = data.len() as u64;
+    let parser = regex(r"\d+")
+        .map(to_int as fn(&str) -> u64)
+        .sep_by(string(","), ..);
+    b.iter(|| parser.parse(black_box(&data)).unwrap().len())
+}
+
+fn map_closure(b: &mut Bencher) {
+    let data = numbers();
+    b.bytes = data.len() as u64;
+    let scale = black_box(1);
+    let parser = regex(r"\d+")
+        .map(move |s| to_int(s) * scale)
+        .sep_by(string(","), ..);
+    b.iter(|| parser.parse(black_box(&data)).unwrap().len())
+}
+
+benchmark_group!(map, map_fn, map_fn_pointer, map_closure);
+
+// ── regex: failing matches inside many() ──────────────────────
+
+/// Words with a single number at the very end, so that the number regex
+/// fails at every word but would find a match if it searched ahead.
+fn words_then_number() -> String {
+    let mut data = "word ".repeat(20_000);
+    data.push_str("42");
+    data
+}
+
+fn regex_failure(b: &mut Bencher) {
+    let data = words_then_number();
+    b.bytes = data.len() as u64;
+    let parser = (regex(r"\d+") | regex("[a-z]+ ")).many(..);
+    b.iter(|| parser.parse(black_box(&data)).unwrap().len())
+}
+
+fn sp_regex_failure(b: &mut Bencher) {
+    let data = words_then_number();
+    b.bytes = data.len() as u64;
+    let parser = sp_regex(r"\d+").or(sp_regex("[a-z]+ ")).many_span(..).into_parser();
+    b.iter(|| parser.parse(black_box(&data)).unwrap().end)
+}
+
+benchmark_group!(regex_fail, regex_failure, sp_regex_failure);
+
+// ── recursive: handles calling back into their parser ─────────
+
+/// Nested lists, a few levels deep, many times over.
+fn nested_lists() -> String {
+    let item = "[[[],[[]]],[],[[[],[]],[[[]]]]]";
+    format!("[{}]", vec![item; 10_000].join(","))
+}
+
+/// The number of lists in a nested list.
+fn list_count<'a>() -> Parser<'a, usize> {
+    recursive(|list| {
+        list.parser()
+            .sep_by(string(","), ..)
+            .wrap(string("["), string("]"))
+            .map(|items| 1 + items.into_iter().sum::<usize>())
+    })
+}
+
+fn recursive_nested(b: &mut Bencher) {
+    let data = nested_lists();
+    b.bytes = data.len() as u64;
+    let parser = list_count();
+    b.iter(|| parser.parse(black_box(&data)).unwrap())
+}
+
+/// One grammar shared by four threads, each parsing the whole input.
+#[cfg(feature = "sync")]
+fn recursive_threads(b: &mut Bencher) {
+    let data = nested_lists();
+    b.bytes = 4 * data.len() as u64;
+    let parser = list_count();
+    b.iter(|| {
+        std::thread::scope(|s| {
+            let threads: Vec<_> = (0..4)
+                .map(|_| s.spawn(|| parser.parse(black_box(&data)).unwrap()))
+                .collect();
+            threads.into_iter().map(|t| t.join().unwrap()).sum::<usize>()
+        })
+    })
+}
+
+#[cfg(not(feature = "sync"))]
+benchmark_group!(recursion, recursive_nested);
+#[cfg(feature = "sync")]
+benchmark_group!(recursion, recursive_nested, recursive_threads);
+
+benchmark_main!(json, map, regex_fail, recursion);
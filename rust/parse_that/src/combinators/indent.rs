@@ -0,0 +1,288 @@
+use crate::parse::Parser;
+use crate::state::ParserState;
+
+// ── Indentation-sensitive layout ──────────────────────────────
+//
+// Offside-rule parsing for Python/YAML-style formats. Each open block keeps
+// its indentation width on `ParserState::indent_stack`; a line belongs to the
+// innermost block it is aligned with, and blank lines never count. Indentation
+// that cannot be reconciled with the open blocks is a committed failure (see
+// `Parser::cut`) carrying an `IndentError`.
+
+/// How whitespace in indentation is counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndentMode {
+    /// Spaces only; a tab is an `IndentError::Tab`.
+    Spaces,
+    /// Tabs only, one level each; a space is an `IndentError::Space`.
+    Tabs,
+    /// Both; a tab advances to the next multiple of `tab_width`.
+    Mixed { tab_width: usize },
+}
+
+impl Default for IndentMode {
+    fn default() -> Self {
+        IndentMode::Mixed { tab_width: 8 }
+    }
+}
+
+/// Why a line's indentation was rejected. Reported as the parse's custom
+/// error (`ParseError::custom_error`, `Diagnostic::custom_error`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentError {
+    /// A tab under `IndentMode::Spaces`.
+    Tab,
+    /// A space under `IndentMode::Tabs`.
+    Space,
+    /// A line indented deeper than its block, outside any nested block.
+    Unexpected { expected: usize, found: usize },
+    /// A dedent to a width that no enclosing block starts at.
+    Inconsistent { found: usize },
+}
+
+impl std::fmt::Display for IndentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndentError::Tab => {
+                write!(f, "inconsistent indentation: tab where spaces are expected")
+            }
+            IndentError::Space => {
+                write!(f, "inconsistent indentation: space where tabs are expected")
+            }
+            IndentError::Unexpected { expected, found } => {
+                write!(f, "unexpected indent: expected {expected}, found {found}")
+            }
+            IndentError::Inconsistent { found } => write!(
+                f,
+                "inconsistent indentation: dedent to {found} matches no enclosing block"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IndentError {}
+
+/// Parse one or more `p`, each starting a line of a block indented deeper
+/// than the enclosing one. The first non-blank line after the current offset
+/// sets the block's indentation; the block ends at the first line indented
+/// less, which must line up with an enclosing block.
+///
+/// ```
+/// use parse_that::*;
+///
+/// let word = || regex("[a-z]+");
+/// let entry = word().skip(string(":")).then(indented_block(word()));
+/// let src = "fruit:\n  apple\n  pear\nveg:\n    leek\n";
+/// let entries = same_indent(entry).many(..).parse(src).unwrap();
+/// assert_eq!(entries[0], ("fruit", vec!["apple", "pear"]));
+/// assert_eq!(entries[1], ("veg", vec!["leek"]));
+/// ```
+pub fn indented_block<'a, O: 'a>(p: Parser<'a, O>) -> Parser<'a, Vec<O>> {
+    let block = move |state: &mut ParserState<'a>| {
+        let Some(line) = next_line(state) else {
+            return state.fail("indented block");
+        };
+        let width = match line.width {
+            Ok(width) => width,
+            Err(error) => return indent_error(state, line.start, line.token, error),
+        };
+        if width <= current_level(state) {
+            return state.fail("indented block");
+        }
+        state.indent_stack.push(width);
+        state.offset = line.token;
+        let items = block_items(&p, state);
+        state.indent_stack.pop();
+        items
+    };
+    Parser::new(block)
+}
+
+/// `p` at the start of the next non-blank line, which must be aligned with the
+/// current block (column 0 outside any `indented_block`). Fails softly at a
+/// dedent or end of input; a deeper line is an `IndentError::Unexpected`.
+pub fn same_indent<'a, O: 'a>(p: Parser<'a, O>) -> Parser<'a, O> {
+    let same_indent = move |state: &mut ParserState<'a>| {
+        align(state)?;
+        p.call(state)
+    };
+    Parser::new(same_indent)
+}
+
+/// Run `p` with line folding: inside it, [`fold_ws`] continues onto following
+/// lines as long as they are indented deeper than the line the fold starts on.
+///
+/// ```
+/// use parse_that::*;
+///
+/// let words = line_fold(regex("[a-z]+").sep_by(fold_ws(), 1..));
+/// let value = string("key: ").next(words);
+/// let (value, state) = value.parse_return_state("key: a long\n  value\nnext: x");
+/// assert_eq!(value, Some(vec!["a", "long", "value"]));
+/// assert_eq!(state.offset, 19);
+/// ```
+pub fn line_fold<'a, O: 'a>(p: Parser<'a, O>) -> Parser<'a, O> {
+    let line_fold = move |state: &mut ParserState<'a>| {
+        let bytes = state.src_bytes;
+        let start = line_start(bytes, state.offset);
+        let token = start + indent_len(&bytes[start..state.end]);
+        let width = match indent_width(&bytes[start..token], state.indent_mode) {
+            Ok(width) => width,
+            Err(error) => return indent_error(state, start, token, error),
+        };
+        let outer = state.fold_indent.replace(width);
+        let result = p.call(state);
+        state.fold_indent = outer;
+        result
+    };
+    Parser::new(line_fold)
+}
+
+/// Whitespace within a [`line_fold`]: spaces and tabs, then a line break if
+/// the next non-blank line is indented deeper than the fold's first line.
+/// Outside a fold, only spaces and tabs. Always succeeds.
+pub fn fold_ws<'a>() -> Parser<'a, ()> {
+    Parser::new(|state: &mut ParserState<'a>| {
+        state.offset += indent_len(&state.src_bytes[state.offset..state.end]);
+        if let Some(fold) = state.fold_indent
+            && let Some(line) = next_line(state)
+            && line.start > state.offset
+            && line.width.is_ok_and(|width| width > fold)
+        {
+            state.offset = line.token;
+        }
+        Some(())
+    })
+}
+
+impl<'a, Output: 'a> Parser<'a, Output> {
+    /// Count indentation with `mode` inside this parser (the default is
+    /// `IndentMode::Mixed { tab_width: 8 }`).
+    pub fn indent_mode(self, mode: IndentMode) -> Parser<'a, Output> {
+        let indent_mode = move |state: &mut ParserState<'a>| {
+            let outer = std::mem::replace(&mut state.indent_mode, mode);
+            let result = self.call(state);
+            state.indent_mode = outer;
+            result
+        };
+        Parser::new(indent_mode)
+    }
+}
+
+struct Line {
+    /// Offset of the line's first byte.
+    start: usize,
+    /// Offset of its first non-whitespace byte.
+    token: usize,
+    width: Result<usize, IndentError>,
+}
+
+fn line_start(bytes: &[u8], offset: usize) -> usize {
+    bytes[..offset]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |nl| nl + 1)
+}
+
+#[inline]
+fn indent_len(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take_while(|&&b| b == b' ' || b == b'\t')
+        .count()
+}
+
+fn indent_width(indent: &[u8], mode: IndentMode) -> Result<usize, IndentError> {
+    indent.iter().try_fold(0, |width, &b| match (b, mode) {
+        (b' ', IndentMode::Tabs) => Err(IndentError::Space),
+        (b'\t', IndentMode::Spaces) => Err(IndentError::Tab),
+        (b'\t', IndentMode::Mixed { tab_width }) => {
+            let tab_width = tab_width.max(1);
+            Ok((width / tab_width + 1) * tab_width)
+        }
+        _ => Ok(width + 1),
+    })
+}
+
+/// The next non-blank line from the current offset. `None` at end of input,
+/// or if the next token is not the first on its line.
+fn next_line(state: &ParserState<'_>) -> Option<Line> {
+    let bytes = state.src_bytes;
+    let token = state.offset
+        + bytes[state.offset..state.end]
+            .iter()
+            .position(|&b| !matches!(b, b' ' | b'\t' | b'\r' | b'\n'))?;
+    let start = line_start(bytes, token);
+    if start + indent_len(&bytes[start..token]) != token {
+        return None;
+    }
+    Some(Line {
+        start,
+        token,
+        width: indent_width(&bytes[start..token], state.indent_mode),
+    })
+}
+
+#[inline]
+fn current_level(state: &ParserState<'_>) -> usize {
+    state.indent_stack.last().copied().unwrap_or(0)
+}
+
+/// Move to the next non-blank line if it is aligned with the current block.
+fn align(state: &mut ParserState<'_>) -> Option<()> {
+    let Some(line) = next_line(state) else {
+        return state.fail("new line");
+    };
+    let width = match line.width {
+        Ok(width) => width,
+        Err(error) => return indent_error(state, line.start, line.token, error),
+    };
+    let level = current_level(state);
+    if width == level {
+        state.offset = line.token;
+        return Some(());
+    }
+    if width > level {
+        let error = IndentError::Unexpected {
+            expected: level,
+            found: width,
+        };
+        return indent_error(state, line.start, line.token, error);
+    }
+    if width != 0 && !state.indent_stack.contains(&width) {
+        return indent_error(
+            state,
+            line.start,
+            line.token,
+            IndentError::Inconsistent { found: width },
+        );
+    }
+    state.fail("dedent")
+}
+
+fn block_items<'a, O: 'a>(p: &Parser<'a, O>, state: &mut ParserState<'a>) -> Option<Vec<O>> {
+    let mut items = vec![p.call(state)?];
+    loop {
+        if align(state).is_none() {
+            return if state.is_cut() { None } else { Some(items) };
+        }
+        items.push(p.call(state)?);
+    }
+}
+
+/// Fail hard at `start`, recording `error` over the indentation up to `token`.
+#[cold]
+fn indent_error<T>(
+    state: &mut ParserState<'_>,
+    start: usize,
+    token: usize,
+    error: IndentError,
+) -> Option<T> {
+    state.offset = token;
+    state.record_error(start, error);
+    state.offset = start;
+    if state.cut_offset.is_none() {
+        state.cut_offset = Some(start);
+    }
+    None
+}
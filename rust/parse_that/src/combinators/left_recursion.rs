@@ -0,0 +1,63 @@
+use crate::combinators::recursive::{Recursive, recursive};
+use crate::parse::Parser;
+use crate::shared::MaybeSync;
+
+// ── Left recursion via seed growing ───────────────────────────
+//
+// Warth, Douglass & Millstein, "Packrat Parsers Can Support Left Recursion"
+// (PEPM '08). On entry at offset `p` the rule plants a failing seed in its
+// memo, so the left-recursive self-call at `p` fails and the base alternative
+// matches. The rule body is then re-run from `p`, the self-call now returning
+// the previous result, for as long as each pass consumes more input. The rule
+// is its body under `memoize_growing`, tied to itself with `recursive`.
+
+/// A reference to a rule under construction by [`left_recursive`]. Cheap to
+/// clone; each `parser()` is a handle that calls back into the one rule.
+pub struct Rule<'a, O> {
+    inner: Recursive<'a, O>,
+}
+
+impl<O> Clone for Rule<'_, O> {
+    fn clone(&self) -> Self {
+        Rule {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, O: Clone + MaybeSync + 'a> Rule<'a, O> {
+    pub fn parser(&self) -> Parser<'a, O> {
+        self.inner.parser()
+    }
+}
+
+/// Define a rule that may refer to itself in left position, such as
+/// `expr = expr "-" number | number`.
+///
+/// `f` receives a [`Rule`] whose handles recurse into the rule being built.
+/// Left-recursive alternatives grow left-associatively, giving the same result
+/// as the hand-refactored `number ("-" number)*` loop. Results are
+/// packrat-memoized on the parse's state, under the restrictions of
+/// [`Parser::memoize`]: the rule must not read or update the user context,
+/// indentation stack or trivia. Indirect left recursion is supported
+/// as long as the cycle passes through a handle of this rule; other rules on
+/// the cycle should not be memoized themselves.
+///
+/// # Examples
+///
+/// ```
+/// use parse_that::*;
+///
+/// let number = || regex(r"\d+").map(|s| s.parse::<i64>().unwrap());
+/// let expr = left_recursive(|expr| {
+///     expr.parser().skip(string("-")).then(number()).map(|(a, b)| a - b) | number()
+/// });
+/// assert_eq!(expr.parse("10-3-2"), Some(5)); // (10 - 3) - 2
+/// ```
+pub fn left_recursive<'a, O, F>(f: F) -> Parser<'a, O>
+where
+    O: Clone + MaybeSync + 'a,
+    F: FnOnce(Rule<'a, O>) -> Parser<'a, O>,
+{
+    recursive(|inner| f(Rule { inner }).memoize_growing())
+}
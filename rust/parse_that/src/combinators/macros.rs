@@ -79,224 +79,352 @@ macro_rules! seq {
 // ── alt!: flat N-ary alternation combinator ───────────────────
 // Creates a single Box<dyn ParserFn> instead of N-1 intermediate boxes.
 // Usage: alt!(p1, p2) → Parser<O>, alt!(p1, p2, p3) → Parser<O>, etc.
+// Like `or`, a failed branch rewinds the user context, and a `cut()` failure
+// stops it.
 
 #[macro_export]
 macro_rules! alt {
     ($p1:expr, $p2:expr) => {
-        $crate::Parser::new(move |state| {
+        $crate::Parser::new($crate::__parser_fn(move |state| {
             let cp = state.offset;
+            let ctx = state.context.clone();
             if let Some(v) = $p1.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p2.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
             None
-        })
+        }))
     };
     ($p1:expr, $p2:expr, $p3:expr) => {
-        $crate::Parser::new(move |state| {
+        $crate::Parser::new($crate::__parser_fn(move |state| {
             let cp = state.offset;
+            let ctx = state.context.clone();
             if let Some(v) = $p1.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p2.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p3.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
             None
-        })
+        }))
     };
     ($p1:expr, $p2:expr, $p3:expr, $p4:expr) => {
-        $crate::Parser::new(move |state| {
+        $crate::Parser::new($crate::__parser_fn(move |state| {
             let cp = state.offset;
+            let ctx = state.context.clone();
             if let Some(v) = $p1.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p2.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p3.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p4.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
             None
-        })
+        }))
     };
     ($p1:expr, $p2:expr, $p3:expr, $p4:expr, $p5:expr) => {
-        $crate::Parser::new(move |state| {
+        $crate::Parser::new($crate::__parser_fn(move |state| {
             let cp = state.offset;
+            let ctx = state.context.clone();
             if let Some(v) = $p1.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p2.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p3.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p4.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p5.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
             None
-        })
+        }))
     };
     ($p1:expr, $p2:expr, $p3:expr, $p4:expr, $p5:expr, $p6:expr) => {
-        $crate::Parser::new(move |state| {
+        $crate::Parser::new($crate::__parser_fn(move |state| {
             let cp = state.offset;
+            let ctx = state.context.clone();
             if let Some(v) = $p1.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p2.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p3.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p4.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p5.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p6.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
             None
-        })
+        }))
     };
     ($p1:expr, $p2:expr, $p3:expr, $p4:expr, $p5:expr, $p6:expr, $p7:expr) => {
-        $crate::Parser::new(move |state| {
+        $crate::Parser::new($crate::__parser_fn(move |state| {
             let cp = state.offset;
+            let ctx = state.context.clone();
             if let Some(v) = $p1.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p2.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p3.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p4.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p5.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p6.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p7.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
             None
-        })
+        }))
     };
     ($p1:expr, $p2:expr, $p3:expr, $p4:expr, $p5:expr, $p6:expr, $p7:expr, $p8:expr) => {
-        $crate::Parser::new(move |state| {
+        $crate::Parser::new($crate::__parser_fn(move |state| {
             let cp = state.offset;
+            let ctx = state.context.clone();
             if let Some(v) = $p1.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p2.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p3.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p4.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p5.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p6.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p7.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
+            if state.is_cut() {
+                return None;
+            }
             if let Some(v) = $p8.call(state) {
                 return Some(v);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = cp;
+            state.restore_context(&ctx);
             None
-        })
+        }))
     };
 }
@@ -2,6 +2,7 @@ use std::ops::RangeBounds;
 
 use crate::leaf::trim_leading_whitespace_mut;
 use crate::parse::Parser;
+use crate::shared::MaybeSync;
 use crate::state::ParserState;
 use crate::utils::extract_bounds;
 use smallvec::SmallVec;
@@ -25,21 +26,29 @@ where
     }
 
     /// Alternation with checkpoint-based backtracking (no Vec push/pop).
+    /// A failed branch rewinds the offset and the user context. A `cut()`
+    /// failure in `self` is not backtracked past.
     #[inline]
     pub fn or(self, other: Parser<'a, Output>) -> Parser<'a, Output> {
         let or = move |state: &mut ParserState<'a>| {
             let checkpoint = state.offset;
+            let context = state.context.clone();
             if let Some(value) = self.call(state) {
                 return Some(value);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = checkpoint;
+            state.restore_context(&context);
+            if state.is_cut() {
+                return None;
+            }
 
             if let Some(value) = other.call(state) {
                 return Some(value);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = checkpoint;
+            state.restore_context(&context);
 
             None
         };
@@ -52,11 +61,122 @@ where
             if let Some(value) = self.call(state) {
                 return Some(Some(value));
             }
+            if state.is_cut() {
+                return None;
+            }
             Some(None)
         };
         Parser::new(opt)
     }
 
+    /// Commit to `self`: if it fails, the failure becomes a hard error that
+    /// enclosing alternations (`or`, `alt!`, `one_of`, `opt`, `many`, ...)
+    /// do not backtrack past, reported at the offset where `self` stopped.
+    /// Equivalent to nom's `cut` and winnow's `cut_err`.
+    ///
+    /// Place it after the input that identifies a branch, so the error points
+    /// at the real failure instead of at the start of the alternative:
+    /// `string("[").then(items.skip(string("]")).cut())`.
+    ///
+    /// The commitment ends with the failure: a parser that swallows it and
+    /// succeeds anyway (a `Parser::new` that rewinds and carries on, or
+    /// `recover`) clears it.
+    #[inline]
+    pub fn cut(self) -> Parser<'a, Output> {
+        let cut = move |state: &mut ParserState<'a>| {
+            let result = self.call(state);
+            if result.is_none() && state.cut_offset.is_none() {
+                state.cut_offset = Some(state.offset);
+            }
+            result
+        };
+        Parser::new(cut)
+    }
+
+    /// `self` then `next`, committed once `self` has matched: shorthand for
+    /// `self.then(next.cut())`.
+    #[inline]
+    pub fn commit<Output2>(self, next: Parser<'a, Output2>) -> Parser<'a, (Output, Output2)>
+    where
+        Output2: 'a,
+    {
+        self.then(next.cut())
+    }
+
+    /// Limit nesting inside `self` to `max_depth` recursive parsers
+    /// (`recursive`, `lazy`, `left_recursive`), so that hostile input fails
+    /// with `LimitError::Depth` instead of overflowing the stack.
+    ///
+    /// ```
+    /// use parse_that::*;
+    /// use parse_that::parsers::json::json_parser;
+    ///
+    /// let hostile = "[".repeat(100_000);
+    /// let parser = json_parser().max_depth(64);
+    /// assert!(parser.parse("[[[1]]]").is_some());
+    /// let err = parser.parse_or_error(&hostile).unwrap_err();
+    /// assert_eq!(err.limit_error, Some(LimitError::Depth));
+    /// ```
+    pub fn max_depth(self, max_depth: usize) -> Parser<'a, Output> {
+        let limited = move |state: &mut ParserState<'a>| {
+            if state.is_cut() || state.limit_error.is_some() {
+                state.clear_failure();
+            }
+            let limit = state.depth.saturating_add(max_depth);
+            let outer = state.max_depth;
+            state.max_depth = Some(outer.map_or(limit, |outer| outer.min(limit)));
+            let result = self.call(state);
+            state.max_depth = outer;
+            result
+        };
+        Parser::new(limited)
+    }
+
+    /// Give `self` a budget of `fuel` steps, one per entry into a recursive
+    /// parser, so that pathological backtracking fails with
+    /// `LimitError::Fuel` instead of running unbounded. Steps taken also
+    /// count against any enclosing budget.
+    pub fn fuel(self, fuel: u64) -> Parser<'a, Output> {
+        let budgeted = move |state: &mut ParserState<'a>| {
+            if state.is_cut() || state.limit_error.is_some() {
+                state.clear_failure();
+            }
+            let outer = state.fuel;
+            let budget = outer.map_or(fuel, |outer| outer.min(fuel));
+            state.fuel = Some(budget);
+            let result = self.call(state);
+            let used = budget - state.fuel.unwrap_or(0);
+            state.fuel = outer.map(|outer| outer - used);
+            result
+        };
+        Parser::new(budgeted)
+    }
+
+    /// Report failures of `self` as `expected <name>`: when it fails without
+    /// consuming input, the expected labels it recorded (`"{"`, `/\d+/`, ...)
+    /// are replaced by `name`. Failures further in keep their precise labels.
+    #[cfg(feature = "diagnostics")]
+    pub fn label(self, name: &'static str) -> Parser<'a, Output> {
+        let label = move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+            let prev_furthest = state.furthest_offset;
+            let prev_expected = state.expected.len();
+            let result = self.call(state);
+            if result.is_none() {
+                state.relabel_expected(start, prev_furthest, prev_expected, name);
+            }
+            result
+        };
+        Parser::new(label)
+    }
+
+    /// No-op without the `diagnostics` feature.
+    #[cfg(not(feature = "diagnostics"))]
+    #[inline]
+    pub fn label(self, _name: &'static str) -> Parser<'a, Output> {
+        self
+    }
+
     /// Consuming negative lookahead: parse `self`, then check that `next` does
     /// NOT match at the resulting position. If `next` matches, the overall
     /// parse fails. Unlike `negate()` (zero-width), `not()` consumes the input
@@ -70,7 +190,7 @@ where
             let value = self.call(state)?;
             let checkpoint = state.offset;
             let saved_furthest = state.furthest_offset;
-            if next.call(state).is_none() {
+            if next.call(state).is_none() && !state.is_cut() {
                 state.offset = checkpoint;
                 state.furthest_offset = saved_furthest;
                 return Some(value);
@@ -92,7 +212,7 @@ where
         let minus = move |state: &mut ParserState<'a>| {
             let checkpoint = state.offset;
             let saved_furthest = state.furthest_offset;
-            if excluded.call(state).is_some() {
+            if excluded.call(state).is_some() || state.is_cut() {
                 state.offset = checkpoint;
                 state.furthest_offset = saved_furthest;
                 return None;
@@ -112,7 +232,7 @@ where
         let negate = move |state: &mut ParserState<'a>| {
             let checkpoint = state.offset;
             let saved_furthest = state.furthest_offset;
-            if self.call(state).is_none() {
+            if self.call(state).is_none() && !state.is_cut() {
                 state.offset = checkpoint;
                 state.furthest_offset = saved_furthest;
                 return Some(());
@@ -141,19 +261,123 @@ where
         Parser::new(peek)
     }
 
+    /// Transform the output. Takes any closure, so configuration (an
+    /// interner, an arena, options) can be captured; fn items and non-capturing
+    /// closures are zero-sized and cost the same as a direct call.
     #[inline]
-    pub fn map<Output2>(self, f: fn(Output) -> Output2) -> Parser<'a, Output2>
+    pub fn map<Output2>(
+        self,
+        f: impl Fn(Output) -> Output2 + MaybeSync + 'a,
+    ) -> Parser<'a, Output2>
     where
         Output2: 'a,
     {
-        let map = move |state: &mut ParserState<'a>| self.call(state).map(f);
+        let map = move |state: &mut ParserState<'a>| self.call(state).map(&f);
         Parser::new(map)
     }
 
+    /// Keep the output only if `pred` accepts it; otherwise fail at the start
+    /// of the value.
+    #[inline]
+    pub fn filter(self, pred: impl Fn(&Output) -> bool + MaybeSync + 'a) -> Parser<'a, Output> {
+        let filter = move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+            let value = self.call(state)?;
+            if pred(&value) {
+                Some(value)
+            } else {
+                state.offset = start;
+                None
+            }
+        };
+        Parser::new(filter)
+    }
+
+    /// Map and filter in one step: `None` fails at the start of the value.
+    #[inline]
+    pub fn filter_map<Output2>(
+        self,
+        f: impl Fn(Output) -> Option<Output2> + MaybeSync + 'a,
+    ) -> Parser<'a, Output2>
+    where
+        Output2: 'a,
+    {
+        let filter_map = move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+            let value = f(self.call(state)?);
+            if value.is_none() {
+                state.offset = start;
+            }
+            value
+        };
+        Parser::new(filter_map)
+    }
+
+    /// `filter` with a reason: on `Err` the parser fails like `try_map`,
+    /// recording the error for `ParseError::custom_error`.
+    #[inline]
+    pub fn validate<E>(
+        self,
+        check: impl Fn(&Output) -> Result<(), E> + MaybeSync + 'a,
+    ) -> Parser<'a, Output>
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let validate = move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+            let value = self.call(state)?;
+            match check(&value) {
+                Ok(()) => Some(value),
+                Err(error) => {
+                    state.record_error(start, error);
+                    state.offset = start;
+                    None
+                }
+            }
+        };
+        Parser::new(validate)
+    }
+
+    /// Map with a fallible check. On `Err` the parser fails at the start of
+    /// the value and records the error, with the value's span, for
+    /// `ParseError::custom_error` and `Diagnostic::custom_error`.
+    ///
+    /// ```
+    /// use parse_that::*;
+    ///
+    /// let byte = regex(r"\d+").try_map(|s| s.parse::<u8>());
+    /// assert_eq!(byte.parse("255"), Some(255));
+    /// let err = byte.parse_or_error("256").unwrap_err();
+    /// assert!(err.custom_error.unwrap().to_string().contains("too large"));
+    /// ```
+    #[inline]
+    pub fn try_map<Output2, E>(
+        self,
+        f: impl Fn(Output) -> Result<Output2, E> + MaybeSync + 'a,
+    ) -> Parser<'a, Output2>
+    where
+        Output2: 'a,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let try_map = move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+            match f(self.call(state)?) {
+                Ok(value) => Some(value),
+                Err(error) => {
+                    state.record_error(start, error);
+                    state.offset = start;
+                    None
+                }
+            }
+        };
+        Parser::new(try_map)
+    }
+
+    /// `map` that also receives the start offset of the value and the state.
     #[inline]
     pub fn map_with_state<Output2>(
         self,
-        f: fn(Output, usize, &mut ParserState<'a>) -> Output2,
+        f: impl Fn(Output, usize, &mut ParserState<'a>) -> Output2 + MaybeSync + 'a,
     ) -> Parser<'a, Output2>
     where
         Output2: 'a,
@@ -166,6 +390,73 @@ where
         Parser::new(map_with_state)
     }
 
+    /// `map` with read access to the user context of type `T` (see
+    /// `ParserState::with_context`). Fails with a custom error if no context
+    /// of type `T` is set.
+    #[inline]
+    pub fn with_state<T, Output2>(
+        self,
+        f: impl Fn(Output, &T) -> Output2 + MaybeSync + 'a,
+    ) -> Parser<'a, Output2>
+    where
+        T: std::any::Any,
+        Output2: 'a,
+    {
+        let with_state = move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+            let value = self.call(state)?;
+            let Some(context) = state.context() else {
+                return missing_context::<T, _>(state, start);
+            };
+            Some(f(value, context))
+        };
+        Parser::new(with_state)
+    }
+
+    /// Update the user context of type `T` after `self` succeeds.
+    ///
+    /// A failed branch of `|`, `alt!` or `one_of` rolls its updates back.
+    /// Other backtracking (`opt`, `many`, `not`, ...) only rewinds the offset;
+    /// mark a parser with `save_state()` to restore the context when it fails
+    /// after an update. For example, a typedef declaration adds its name to a
+    /// symbol table, unless its `;` is missing:
+    ///
+    /// ```
+    /// use parse_that::*;
+    /// use std::collections::HashSet;
+    ///
+    /// let ident = || regex("[a-z]+").trim_whitespace();
+    /// let typedef = string("typedef")
+    ///     .next(ident())
+    ///     .update_state(|name: &&str, types: &mut HashSet<String>| {
+    ///         types.insert(name.to_string());
+    ///     })
+    ///     .skip(string(";").trim_whitespace());
+    /// let src = "typedef a; typedef b";
+    /// let decls = (typedef | ident()).many(..);
+    /// let (names, types) = decls.parse_with_context(src, HashSet::<String>::new());
+    /// assert_eq!(names, Some(vec!["a", "typedef", "b"]));
+    /// assert!(types.contains("a") && !types.contains("b"));
+    /// ```
+    ///
+    /// Fails with a custom error if no context of type `T` is set.
+    #[inline]
+    pub fn update_state<T>(self, f: impl Fn(&Output, &mut T) + MaybeSync + 'a) -> Parser<'a, Output>
+    where
+        T: std::any::Any,
+    {
+        let update_state = move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+            let value = self.call(state)?;
+            let Some(context) = state.context_mut() else {
+                return missing_context::<T, _>(state, start);
+            };
+            f(&value, context);
+            Some(value)
+        };
+        Parser::new(update_state)
+    }
+
     #[inline]
     pub fn skip<Output2>(self, next: Parser<'a, Output2>) -> Parser<'a, Output>
     where
@@ -218,6 +509,9 @@ where
                     // binding matched but comma didn't). Without this, subsequent
                     // parsers (like `.then(expression)`) see a wrong offset.
                     state.offset = prev_offset;
+                    if state.is_cut() {
+                        return None;
+                    }
                     break;
                 }
             }
@@ -255,6 +549,9 @@ where
                     }
                 } else {
                     state.offset = prev_offset;
+                    if state.is_cut() {
+                        return None;
+                    }
                     break;
                 }
             }
@@ -288,7 +585,7 @@ where
             // Parse first element
             if let Some(value) = self.call(state) {
                 values.push(value);
-            } else if lower_bound == 0 {
+            } else if lower_bound == 0 && !state.is_cut() {
                 return Some(values);
             } else {
                 return None;
@@ -308,6 +605,10 @@ where
                 }
             }
 
+            if state.is_cut() {
+                return None;
+            }
+
             if values.len() >= lower_bound {
                 Some(values)
             } else {
@@ -422,7 +723,7 @@ where
             // Parse first element
             if let Some(value) = self.call(state) {
                 values.push(value);
-            } else if lower_bound == 0 {
+            } else if lower_bound == 0 && !state.is_cut() {
                 return Some(values);
             } else {
                 return None;
@@ -446,6 +747,10 @@ where
                 }
             }
 
+            if state.is_cut() {
+                return None;
+            }
+
             if values.len() >= lower_bound {
                 Some(values)
             } else {
@@ -480,7 +785,7 @@ where
             // Parse first element
             if let Some(value) = self.call(state) {
                 values.push(value);
-            } else if lower_bound == 0 {
+            } else if lower_bound == 0 && !state.is_cut() {
                 return Some(values);
             } else {
                 return None;
@@ -505,6 +810,10 @@ where
                 }
             }
 
+            if state.is_cut() {
+                return None;
+            }
+
             if values.len() >= lower_bound {
                 // Post-trim after the last element
                 trim_leading_whitespace_mut(state);
@@ -539,7 +848,7 @@ where
 
             if let Some(value) = self.call(state) {
                 values.push(value);
-            } else if lower_bound == 0 {
+            } else if lower_bound == 0 && !state.is_cut() {
                 return Some(values);
             } else {
                 return None;
@@ -549,10 +858,10 @@ where
                 let cp = state.offset;
                 trim_leading_whitespace_mut(state);
                 // Peek terminator — skip separator attempt entirely
-                if let Some(&b) = state.src_bytes.get(state.offset) {
-                    if terminator.contains(&b) {
-                        break;
-                    }
+                if let Some(&b) = state.src_bytes.get(state.offset)
+                    && terminator.contains(&b)
+                {
+                    break;
                 }
                 if sep.parser_fn.call(state).is_none() {
                     state.offset = cp;
@@ -567,6 +876,10 @@ where
                 }
             }
 
+            if state.is_cut() {
+                return None;
+            }
+
             if values.len() >= lower_bound {
                 trim_leading_whitespace_mut(state);
                 Some(values)
@@ -585,10 +898,12 @@ where
     ///
     /// This enables `many()` / `sep_by()` loops to keep going — each failed
     /// element produces a diagnostic but doesn't halt the overall parse.
+    /// `cut()` failures are recovered from too; the diagnostic records the
+    /// cut location.
     #[cfg(feature = "diagnostics")]
     pub fn recover(self, sync: Parser<'a, ()>, sentinel: Output) -> Parser<'a, Output>
     where
-        Output: Clone,
+        Output: Clone + MaybeSync,
     {
         use crate::state::{pop_last_diagnostic, push_diagnostic};
 
@@ -602,6 +917,8 @@ where
             let diag = state.snapshot_diagnostic(checkpoint);
             push_diagnostic(diag);
 
+            // A committed failure stops at the recovery point
+            let cut = state.cut_offset.take();
             state.offset = checkpoint;
             if sync.call(state).is_some() {
                 // Sync succeeded — return sentinel
@@ -609,6 +926,7 @@ where
             } else {
                 // Sync failed — pop the diagnostic and give up
                 pop_last_diagnostic();
+                state.cut_offset = cut;
                 state.offset = checkpoint;
                 None
             }
@@ -620,7 +938,7 @@ where
     #[cfg(not(feature = "diagnostics"))]
     pub fn recover(self, _sync: Parser<'a, ()>, _sentinel: Output) -> Parser<'a, Output>
     where
-        Output: Clone,
+        Output: Clone + MaybeSync,
     {
         panic!("recover() requires the `diagnostics` feature")
     }
@@ -634,7 +952,7 @@ where
     pub fn chain<Output2, F>(self, f: F) -> Parser<'a, Output2>
     where
         Output2: 'a,
-        F: Fn(Output) -> Parser<'a, Output2> + 'a,
+        F: Fn(Output) -> Parser<'a, Output2> + MaybeSync + 'a,
     {
         let chain = move |state: &mut ParserState<'a>| {
             let value = self.call(state)?;
@@ -663,40 +981,120 @@ where
     /// Packrat memoization: cache parse results by input offset.
     /// On cache hit, restores offset and returns cloned value in O(1).
     /// Eliminates exponential re-parsing in ambiguous/cyclic grammars.
+    ///
+    /// The cache lives on the `ParserState` (see `MemoTables`), so each parse
+    /// starts fresh and the parser itself holds no results.
+    ///
+    /// Results are keyed by offset alone. A memoized parser must not depend on
+    /// the user context, the indentation stack or the active `Trivia`, which
+    /// may differ between two visits to the same offset, and must not update
+    /// the context: a cache hit replays the value, not its side effects.
     pub fn memoize(self) -> Parser<'a, Output>
     where
-        Output: Clone,
+        Output: Clone + MaybeSync,
     {
-        use std::cell::RefCell;
-        use std::collections::HashMap;
+        self.memoize_with_capacity(crate::state::MEMO_DEFAULT_CAPACITY)
+    }
 
-        // Cache: offset → None (failed) | Some((end_offset, value))
-        let cache: RefCell<HashMap<usize, Option<(usize, Output)>>> =
-            RefCell::new(HashMap::new());
+    /// `memoize()` keeping at most `capacity` results per parse, evicting the
+    /// lowest offset first; 0 disables caching.
+    pub fn memoize_with_capacity(self, capacity: usize) -> Parser<'a, Output>
+    where
+        Output: Clone + MaybeSync,
+    {
+        let id = crate::state::MemoId::new();
 
         let memo = move |state: &mut ParserState<'a>| {
             let key = state.offset;
 
-            // Fast path: check cache without mutation
-            if let Some(entry) = cache.borrow().get(&key) {
+            if let Some(entry) = state.memo.table::<Output>(id, capacity).get(key) {
                 return match entry {
                     Some((end_offset, value)) => {
-                        state.offset = *end_offset;
-                        Some(value.clone())
+                        state.offset = end_offset;
+                        Some(value)
                     }
                     None => None,
                 };
             }
 
-            // Cache miss: parse and store result
+            // Cache miss: parse and store result. Committed failures are not
+            // cached, since a hit could not replay the cut.
             let result = self.call(state);
+            if result.is_none() && state.is_cut() {
+                return None;
+            }
             let entry = result.as_ref().map(|v| (state.offset, v.clone()));
-            cache.borrow_mut().insert(key, entry);
+            state.memo.table(id, capacity).insert(key, entry);
             result
         };
 
         Parser::new(memo)
     }
+
+    /// `memoize()` that grows a seed at each offset, for `left_recursive`.
+    ///
+    /// A call at an offset with no entry plants a failing seed there, so a
+    /// left-recursive call back into this parser at the same offset fails,
+    /// then re-runs `self` for as long as each pass consumes more input, the
+    /// seed holding the previous pass's result.
+    pub(crate) fn memoize_growing(self) -> Parser<'a, Output>
+    where
+        Output: Clone + MaybeSync,
+    {
+        let id = crate::state::MemoId::new();
+        let capacity = crate::state::MEMO_DEFAULT_CAPACITY;
+
+        let memo = move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+
+            if let Some(entry) = state.memo.table::<Output>(id, capacity).get(start) {
+                let (end, value) = entry?;
+                state.offset = end;
+                return Some(value);
+            }
+
+            state.memo.table::<Output>(id, capacity).plant(start, None);
+            let mut best: Option<(usize, Output)> = None;
+            loop {
+                state.offset = start;
+                let Some(value) = self.call(state) else {
+                    if state.is_cut() {
+                        // As in `memoize()`, committed failures are not cached
+                        state.memo.table::<Output>(id, capacity).uproot(start);
+                        return None;
+                    }
+                    break;
+                };
+                if best.as_ref().is_some_and(|(end, _)| state.offset <= *end) {
+                    break;
+                }
+                best = Some((state.offset, value));
+                state.memo.table(id, capacity).plant(start, best.clone());
+            }
+            state.memo.table(id, capacity).settle(start, best.clone());
+
+            match best {
+                Some((end, value)) => {
+                    state.offset = end;
+                    Some(value)
+                }
+                None => {
+                    state.offset = start;
+                    None
+                }
+            }
+        };
+
+        Parser::new(memo)
+    }
+}
+
+#[cold]
+fn missing_context<T, O>(state: &mut ParserState<'_>, start: usize) -> Option<O> {
+    let error = format!("no parser context of type `{}`", std::any::type_name::<T>());
+    state.record_error(start, error);
+    state.offset = start;
+    None
 }
 
 impl<'a, Output2> std::ops::BitOr<Parser<'a, Output2>> for Parser<'a, Output2>
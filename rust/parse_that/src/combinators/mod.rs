@@ -1,5 +1,17 @@
 mod methods;
 pub use methods::*;
 
+mod left_recursion;
+pub use left_recursion::*;
+
+mod recursive;
+pub use recursive::*;
+
+mod pratt;
+pub use pratt::*;
+
+mod indent;
+pub use indent::*;
+
 #[allow(clippy::module_inception)]
 mod macros;
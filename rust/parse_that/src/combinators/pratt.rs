@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+
+use crate::leaf::{dispatch_byte, trim_leading_whitespace_mut};
+use crate::parse::Parser;
+use crate::shared::MaybeSync;
+use crate::state::ParserState;
+
+// ── Pratt / operator-precedence expressions ───────────────────
+//
+// One loop replaces a `chainl1` ladder per precedence level: after each
+// operand, the next operator is found by first-byte dispatch and either binds
+// (its binding power is at least the caller's minimum) or hands the operand
+// back to the enclosing level. Binding powers are doubled internally so that
+// associativity is the `+1` on one side.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Assoc {
+    Left,
+    Right,
+    /// `a == b == c` is rejected.
+    None,
+}
+
+#[cfg(not(feature = "sync"))]
+type UnaryFold<'a, O> = Box<dyn Fn(O) -> O + 'a>;
+#[cfg(not(feature = "sync"))]
+type BinaryFold<'a, O> = Box<dyn Fn(O, O) -> O + 'a>;
+#[cfg(feature = "sync")]
+type UnaryFold<'a, O> = Box<dyn Fn(O) -> O + Send + Sync + 'a>;
+#[cfg(feature = "sync")]
+type BinaryFold<'a, O> = Box<dyn Fn(O, O) -> O + Send + Sync + 'a>;
+
+struct Unary<'a, O> {
+    token: &'static str,
+    bp: u16,
+    fold: UnaryFold<'a, O>,
+}
+
+struct Binary<'a, O> {
+    token: &'static str,
+    left_bp: u16,
+    right_bp: u16,
+    assoc: Assoc,
+    fold: BinaryFold<'a, O>,
+}
+
+/// Builder for an operator-precedence parser; see [`pratt`].
+pub struct Pratt<'a, O> {
+    atom: Parser<'a, O>,
+    prefix: Vec<Unary<'a, O>>,
+    infix: Vec<Binary<'a, O>>,
+    postfix: Vec<Unary<'a, O>>,
+}
+
+/// Start an expression parser over `atom` (numbers, identifiers,
+/// parenthesized sub-expressions, ...). Register operators with
+/// [`Pratt::prefix`], [`Pratt::infix`] and [`Pratt::postfix`], then call
+/// [`Pratt::build`].
+///
+/// Precedence is a `u8` where higher binds tighter. Operators are literal
+/// tokens, matched longest-first among those sharing a first byte, and
+/// whitespace around them is skipped. Word-like tokens such as `and` need a
+/// word boundary after them. A missing operand fails with the
+/// diagnostics label `"operand"`.
+///
+/// # Examples
+///
+/// ```
+/// use parse_that::*;
+///
+/// let number = regex(r"\d+").map(|s| s.parse::<i64>().unwrap());
+/// let expr = pratt(number)
+///     .infix("+", Assoc::Left, 1, |a, b| a + b)
+///     .infix("-", Assoc::Left, 1, |a, b| a - b)
+///     .infix("*", Assoc::Left, 2, |a, b| a * b)
+///     .infix("^", Assoc::Right, 3, |a, b| a.pow(b as u32))
+///     .prefix("-", 4, |a| -a)
+///     .postfix("!", 5, |a| (1..=a).product())
+///     .build();
+/// assert_eq!(expr.parse("1 - 2 * 3! + 2 ^ 3 ^ 2"), Some(1 - 2 * 6 + 512));
+/// assert_eq!(expr.parse("-2 ^ 2"), Some(4));
+/// ```
+pub fn pratt<'a, O: 'a>(atom: Parser<'a, O>) -> Pratt<'a, O> {
+    Pratt {
+        atom,
+        prefix: Vec::new(),
+        infix: Vec::new(),
+        postfix: Vec::new(),
+    }
+}
+
+impl<'a, O: 'a> Pratt<'a, O> {
+    pub fn prefix(
+        mut self,
+        token: &'static str,
+        prec: u8,
+        fold: impl Fn(O) -> O + MaybeSync + 'a,
+    ) -> Self {
+        self.prefix.push(Unary {
+            token,
+            bp: prec as u16 * 2 + 1,
+            fold: Box::new(fold),
+        });
+        self
+    }
+
+    pub fn infix(
+        mut self,
+        token: &'static str,
+        assoc: Assoc,
+        prec: u8,
+        fold: impl Fn(O, O) -> O + MaybeSync + 'a,
+    ) -> Self {
+        let bp = prec as u16 * 2 + 1;
+        let (left_bp, right_bp) = match assoc {
+            Assoc::Left | Assoc::None => (bp, bp + 1),
+            Assoc::Right => (bp + 1, bp),
+        };
+        self.infix.push(Binary {
+            token,
+            left_bp,
+            right_bp,
+            assoc,
+            fold: Box::new(fold),
+        });
+        self
+    }
+
+    pub fn postfix(
+        mut self,
+        token: &'static str,
+        prec: u8,
+        fold: impl Fn(O) -> O + MaybeSync + 'a,
+    ) -> Self {
+        self.postfix.push(Unary {
+            token,
+            bp: prec as u16 * 2 + 1,
+            fold: Box::new(fold),
+        });
+        self
+    }
+
+    pub fn build(self) -> Parser<'a, O> {
+        let expr = PrattParser {
+            prefix_ops: operator_table(self.prefix.iter().map(|op| op.token)),
+            infix_ops: operator_table(self.infix.iter().map(|op| op.token)),
+            postfix_ops: operator_table(self.postfix.iter().map(|op| op.token)),
+            atom: self.atom,
+            prefix: self.prefix,
+            infix: self.infix,
+            postfix: self.postfix,
+        };
+        Parser::new(move |state: &mut ParserState<'a>| expr.expr(state, 0))
+    }
+}
+
+/// Match any of `tokens`, yielding its index. Candidates are bucketed by first
+/// byte for `dispatch_byte`, longest first within a bucket. A token ending in
+/// an identifier byte only matches at a word boundary, so `or` is not found
+/// at the start of `order`.
+fn operator_table<'a>(tokens: impl Iterator<Item = &'static str>) -> Option<Parser<'a, usize>> {
+    let mut buckets: BTreeMap<u8, Vec<(usize, &'static [u8])>> = BTreeMap::new();
+    for (i, token) in tokens.enumerate() {
+        let bytes = token.as_bytes();
+        assert!(
+            !bytes.is_empty(),
+            "pratt: operator tokens must be non-empty"
+        );
+        buckets.entry(bytes[0]).or_default().push((i, bytes));
+    }
+    if buckets.is_empty() {
+        return None;
+    }
+    let table = buckets
+        .into_iter()
+        .map(|(byte, mut candidates)| {
+            candidates.sort_by_key(|(_, token)| std::cmp::Reverse(token.len()));
+            let matcher = Parser::new(move |state: &mut ParserState<'a>| {
+                let rest = &state.src_bytes[state.offset..];
+                let &(i, token) = candidates.iter().find(|(_, t)| {
+                    rest.starts_with(t)
+                        && !(is_ident_byte(t[t.len() - 1])
+                            && rest.get(t.len()).is_some_and(|&b| is_ident_byte(b)))
+                })?;
+                state.offset += token.len();
+                Some(i)
+            });
+            (byte, matcher)
+        })
+        .collect();
+    Some(dispatch_byte(table))
+}
+
+/// ASCII letters, digits and `_`, plus any non-ASCII byte (part of a Unicode
+/// identifier character).
+#[inline]
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || !b.is_ascii()
+}
+
+struct PrattParser<'a, O> {
+    atom: Parser<'a, O>,
+    prefix: Vec<Unary<'a, O>>,
+    infix: Vec<Binary<'a, O>>,
+    postfix: Vec<Unary<'a, O>>,
+    prefix_ops: Option<Parser<'a, usize>>,
+    infix_ops: Option<Parser<'a, usize>>,
+    postfix_ops: Option<Parser<'a, usize>>,
+}
+
+impl<'a, O: 'a> PrattParser<'a, O> {
+    /// Try `ops` at the next token (after whitespace); on a miss the offset is
+    /// left where it was.
+    #[inline]
+    fn peek_op(ops: &Option<Parser<'a, usize>>, state: &mut ParserState<'a>) -> Option<usize> {
+        let ops = ops.as_ref()?;
+        let checkpoint = state.offset;
+        trim_leading_whitespace_mut(state);
+        let found = ops.call(state);
+        if found.is_none() {
+            state.offset = checkpoint;
+        }
+        found
+    }
+
+    fn operand(&self, state: &mut ParserState<'a>) -> Option<O> {
+        if let Some(i) = Self::peek_op(&self.prefix_ops, state) {
+            let op = &self.prefix[i];
+            let rhs = self.expr(state, op.bp)?;
+            return Some((op.fold)(rhs));
+        }
+        trim_leading_whitespace_mut(state);
+        match self.atom.call(state) {
+            Some(atom) => Some(atom),
+            None => state.fail("operand"),
+        }
+    }
+
+    /// Prefix operands and right operands recurse through here, so each
+    /// level counts against `max_depth` and `fuel` like a recursive parser.
+    fn expr(&self, state: &mut ParserState<'a>, min_bp: u16) -> Option<O> {
+        state.enter()?;
+        let result = self.expr_at(state, min_bp);
+        state.leave();
+        result
+    }
+
+    fn expr_at(&self, state: &mut ParserState<'a>, min_bp: u16) -> Option<O> {
+        let mut lhs = self.operand(state)?;
+        // Binding power of the last non-associative operator applied at this level
+        let mut non_assoc_bp = None;
+
+        loop {
+            let checkpoint = state.offset;
+
+            if let Some(i) = Self::peek_op(&self.postfix_ops, state) {
+                let op = &self.postfix[i];
+                if op.bp < min_bp {
+                    state.offset = checkpoint;
+                    break;
+                }
+                lhs = (op.fold)(lhs);
+                continue;
+            }
+
+            let Some(i) = Self::peek_op(&self.infix_ops, state) else {
+                break;
+            };
+            let op = &self.infix[i];
+            if op.left_bp < min_bp {
+                state.offset = checkpoint;
+                break;
+            }
+            if non_assoc_bp == Some(op.left_bp) {
+                state.offset = checkpoint;
+                return state.fail("end of non-associative expression");
+            }
+            let rhs = self.expr(state, op.right_bp)?;
+            lhs = (op.fold)(lhs, rhs);
+            non_assoc_bp = (op.assoc == Assoc::None).then_some(op.left_bp);
+        }
+        Some(lhs)
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(not(feature = "sync"))]
+use std::{
+    cell::OnceCell,
+    rc::{Rc, Weak},
+};
+// Handles share one parser; under `sync` it must be thread-safe
+#[cfg(feature = "sync")]
+use std::sync::{Arc as Rc, OnceLock as OnceCell, Weak};
+
+use crate::parse::Parser;
+use crate::state::ParserState;
+
+// ── Shared parsers and recursion ──────────────────────────────
+//
+// A `Parser` owns its boxed function, so it cannot be cloned: using a
+// sub-grammar twice means building it twice, and a grammar that refers to
+// itself through `lazy` builds a fresh copy at every level of nesting.
+// `shared()` puts a parser behind a reference count instead, and `recursive`
+// ties the knot once, handing the body a handle to the parser it returns.
+
+/// A parser behind a reference count, from [`Parser::shared`]. Cheap to
+/// clone; each `parser()` is a handle that calls the one shared parser.
+pub struct SharedParser<'a, O> {
+    inner: Rc<Parser<'a, O>>,
+}
+
+impl<O> Clone for SharedParser<'_, O> {
+    fn clone(&self) -> Self {
+        SharedParser {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, O: 'a> SharedParser<'a, O> {
+    pub fn parser(&self) -> Parser<'a, O> {
+        let inner = self.inner.clone();
+        Parser::new(move |state: &mut ParserState<'a>| inner.call(state))
+    }
+}
+
+impl<'a, Output: 'a> Parser<'a, Output> {
+    /// Share this parser, so that it can be used in several places without
+    /// being built again.
+    ///
+    /// ```
+    /// use parse_that::*;
+    ///
+    /// let number = regex(r"\d+").map(|s| s.parse::<u32>().unwrap()).shared();
+    /// let pair = number.parser().skip(string(",")).then(number.parser());
+    /// assert_eq!(pair.parse("3,4"), Some((3, 4)));
+    /// ```
+    pub fn shared(self) -> SharedParser<'a, Output> {
+        SharedParser {
+            inner: Rc::new(self),
+        }
+    }
+}
+
+/// A reference to the parser being built by [`recursive`]. Cheap to clone;
+/// each `parser()` is a handle that calls back into that parser.
+///
+/// Handles do not own the parser (that would be a cycle); each call holds a
+/// strong reference for its duration. A handle called after its parser was
+/// dropped panics.
+pub struct Recursive<'a, O> {
+    inner: Weak<OnceCell<Parser<'a, O>>>,
+}
+
+impl<O> Clone for Recursive<'_, O> {
+    fn clone(&self) -> Self {
+        Recursive {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, O: 'a> Recursive<'a, O> {
+    pub fn parser(&self) -> Parser<'a, O> {
+        let inner = self.inner.clone();
+        Parser::new(move |state: &mut ParserState<'a>| {
+            let Some(cell) = inner.upgrade() else {
+                panic!("recursive: handle outlived its parser");
+            };
+            let parser = cell
+                .get()
+                .expect("recursive: parser invoked before it was built");
+            state.enter()?;
+            let result = parser.call(state);
+            state.leave();
+            result
+        })
+    }
+}
+
+/// Build a parser that refers to itself. `f` receives a [`Recursive`] whose
+/// handles call the parser `f` returns, so the grammar is built exactly once
+/// however deeply the input nests. For left recursion, see
+/// [`left_recursive`](crate::left_recursive).
+///
+/// ```
+/// use parse_that::*;
+///
+/// // list = "[" (list ("," list)*)? "]"
+/// let depth = recursive(|list| {
+///     list.parser()
+///         .sep_by(string(","), ..)
+///         .wrap(string("["), string("]"))
+///         .map(|items| 1 + items.into_iter().max().unwrap_or(0))
+/// });
+/// assert_eq!(depth.parse("[[],[[]]]"), Some(3));
+/// ```
+pub fn recursive<'a, O, F>(f: F) -> Parser<'a, O>
+where
+    O: 'a,
+    F: FnOnce(Recursive<'a, O>) -> Parser<'a, O>,
+{
+    let cell = Rc::new(OnceCell::new());
+    let body = f(Recursive {
+        inner: Rc::downgrade(&cell),
+    });
+    let _ = cell.set(body);
+    Parser::new(move |state: &mut ParserState<'a>| {
+        cell.get()
+            .expect("recursive: parser invoked before it was built")
+            .call(state)
+    })
+}
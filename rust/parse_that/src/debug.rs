@@ -4,6 +4,8 @@ use crate::state::ParserState;
 #[cfg(feature = "diagnostics")]
 use colored::{Color, Colorize};
 #[cfg(feature = "diagnostics")]
+use crate::line_index::{LineCol, LineIndex};
+#[cfg(feature = "diagnostics")]
 use smallvec::SmallVec;
 
 #[cfg(feature = "diagnostics")]
@@ -67,22 +69,51 @@ fn line_number_width(max_line: usize) -> usize {
     max_line.to_string().len()
 }
 
+/// Number of lines as `str::lines` counts them: no empty last line after a
+/// trailing newline.
+#[cfg(feature = "diagnostics")]
+fn display_line_count(src: &str, index: &LineIndex) -> usize {
+    index.line_count() - usize::from(src.is_empty() || src.ends_with('\n'))
+}
+
+/// Text of 1-based `line`, without its line ending.
+#[cfg(feature = "diagnostics")]
+fn line_text<'s>(src: &'s str, index: &LineIndex, line: usize) -> &'s str {
+    let text = index.line_range(line).map_or("", |range| &src[range]);
+    text.strip_suffix('\r').unwrap_or(text)
+}
+
 #[cfg(feature = "diagnostics")]
 pub fn add_cursor(state: &ParserState, cursor: &str, error: bool) -> String {
+    render_cursor(state.src, state.line_index(), state.offset, cursor, error)
+}
+
+#[cfg(feature = "diagnostics")]
+fn render_cursor(
+    src: &str,
+    index: &LineIndex,
+    offset: usize,
+    cursor: &str,
+    error: bool,
+) -> String {
     let color_fn = if error { Color::Red } else { Color::Green };
 
-    let line_num = state.get_line_number(); // 1-based
-    let column_num = state.get_column_number(); // 0-based
+    // 1-based line, 0-based column
+    let LineCol {
+        line: line_num,
+        col: column_num,
+        ..
+    } = index.line_col(offset);
 
-    let lines: Vec<&str> = state.src.lines().collect();
     let start_idx = line_num.saturating_sub(MAX_LINES + 1);
-    let end_idx = (line_num + MAX_LINES).min(lines.len());
+    let end_idx = (line_num + MAX_LINES).min(display_line_count(src, index));
 
     let ln_width = line_number_width(end_idx);
 
     let mut result = Vec::new();
 
-    for (i, line_text) in lines.iter().enumerate().take(end_idx).skip(start_idx) {
+    for i in start_idx..end_idx {
+        let line_text = line_text(src, index, i + 1);
         let line_content =
             summarize_line(line_text, if i == line_num - 1 { column_num } else { 0 });
         let ln = i + 1; // 1-based display
@@ -112,32 +143,34 @@ pub fn add_cursor(state: &ParserState, cursor: &str, error: bool) -> String {
 
 #[cfg(feature = "diagnostics")]
 pub fn format_secondary_spans(state: &ParserState) -> String {
+    render_secondary_spans(state.src, state.line_index(), &state.secondary_spans)
+}
+
+#[cfg(feature = "diagnostics")]
+fn render_secondary_spans(
+    src: &str,
+    index: &LineIndex,
+    spans: &[crate::state::SecondarySpan],
+) -> String {
     let mut result = Vec::new();
 
-    for span in &state.secondary_spans {
-        let lines: Vec<&str> = state.src.lines().collect();
-        // Find line containing this offset
-        let mut offset_acc = 0;
-        for (i, line) in lines.iter().enumerate() {
-            let line_end = offset_acc + line.len() + 1; // +1 for newline
-            if span.offset < line_end {
-                let col = span.offset - offset_acc;
-                let ln_width = (i + 1).to_string().len().max(3);
-                let pipe = "|".color(Color::BrightBlack).to_string();
-                let ln_str = format!("{:>width$}", i + 1, width = ln_width);
-                let ln_display = ln_str.color(Color::BrightBlack).to_string();
-
-                result.push(format!(" {} {}", " ".repeat(ln_width), pipe));
-                result.push(format!(" {} {} {}", ln_display, pipe, lines[i]));
-
-                let marker_pad = " ".repeat(ln_width + 3 + col);
-                let marker = "-".color(Color::Cyan).to_string();
-                let label = span.label.color(Color::Cyan).to_string();
-                result.push(format!("{}{} {}", marker_pad, marker, label));
-                break;
-            }
-            offset_acc = line_end;
+    for span in spans {
+        let LineCol { line, col, .. } = index.line_col(span.offset);
+        if line > display_line_count(src, index) {
+            continue;
         }
+        let ln_width = line.to_string().len().max(3);
+        let pipe = "|".color(Color::BrightBlack).to_string();
+        let ln_str = format!("{:>width$}", line, width = ln_width);
+        let ln_display = ln_str.color(Color::BrightBlack).to_string();
+
+        result.push(format!(" {} {}", " ".repeat(ln_width), pipe));
+        result.push(format!(" {} {} {}", ln_display, pipe, line_text(src, index, line)));
+
+        let marker_pad = " ".repeat(ln_width + 3 + col);
+        let marker = "-".color(Color::Cyan).to_string();
+        let label = span.label.color(Color::Cyan).to_string();
+        result.push(format!("{}{} {}", marker_pad, marker, label));
     }
 
     result.join("\n")
@@ -235,6 +268,11 @@ pub fn state_print(
 
 #[cfg(feature = "diagnostics")]
 pub fn format_diagnostic(d: &crate::state::Diagnostic, src: &str) -> String {
+    render_diagnostic(d, src, &LineIndex::new(src))
+}
+
+#[cfg(feature = "diagnostics")]
+fn render_diagnostic(d: &crate::state::Diagnostic, src: &str, index: &LineIndex) -> String {
     let badge = " Err x ".on_color(Color::Red).bold().to_string();
     let loc = format!("{}:{}", d.line, d.column)
         .color(Color::BrightBlack)
@@ -242,10 +280,7 @@ pub fn format_diagnostic(d: &crate::state::Diagnostic, src: &str) -> String {
     let offset_str = d.furthest_offset.to_string().color(Color::Green).to_string();
     let header = format!("{}    {}    {}", badge, loc, offset_str);
 
-    // Build a temporary ParserState for add_cursor
-    let mut tmp_state = ParserState::new(src);
-    tmp_state.offset = d.furthest_offset;
-    let body = add_cursor(&tmp_state, "^^^", true);
+    let body = render_cursor(src, index, d.furthest_offset, "^^^", true);
 
     let mut output = format!("{}\n{}", header, body);
 
@@ -256,11 +291,19 @@ pub fn format_diagnostic(d: &crate::state::Diagnostic, src: &str) -> String {
         output.push_str(&format!("\n   {}", expected_display));
     }
 
+    if let Some(error) = &d.custom_error {
+        let error_display = error.to_string().color(Color::Red).to_string();
+        output.push_str(&format!("\n   {}", error_display));
+    }
+
+    if let Some(error) = &d.limit_error {
+        let error_display = error.to_string().color(Color::Red).to_string();
+        output.push_str(&format!("\n   {}", error_display));
+    }
+
     if !d.secondary_spans.is_empty() {
-        // Build temporary state with secondary spans for rendering
-        let mut span_state = ParserState::new(src);
-        span_state.secondary_spans = SmallVec::from_vec(d.secondary_spans.clone());
-        output.push_str(&format!("\n{}", format_secondary_spans(&span_state)));
+        let spans = render_secondary_spans(src, index, &d.secondary_spans);
+        output.push_str(&format!("\n{}", spans));
     }
 
     if !d.suggestions.is_empty() {
@@ -284,7 +327,12 @@ pub fn format_all_diagnostics(diagnostics: &[crate::state::Diagnostic], src: &st
         return String::new();
     }
 
-    let parts: Vec<String> = diagnostics.iter().map(|d| format_diagnostic(d, src)).collect();
+    // One index for all of them: formatting is then linear in the source
+    let index = LineIndex::new(src);
+    let parts: Vec<String> = diagnostics
+        .iter()
+        .map(|d| render_diagnostic(d, src, &index))
+        .collect();
     let count = diagnostics.len();
     let summary = format!(
         "{} error{} found",
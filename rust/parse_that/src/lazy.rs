@@ -1,16 +1,18 @@
+#[cfg(not(feature = "sync"))]
 use std::cell::UnsafeCell;
 
 use crate::parse::Parser;
+use crate::shared::MaybeSync;
 use crate::state::ParserState;
 
-pub trait LazyParserFn<'a, Output>: 'a {
+pub trait LazyParserFn<'a, Output>: MaybeSync + 'a {
     fn call(&self) -> Parser<'a, Output>;
 }
 
 impl<'a, Output, F> LazyParserFn<'a, Output> for F
 where
     Output: 'a,
-    F: Fn() -> Parser<'a, Output> + 'a,
+    F: Fn() -> Parser<'a, Output> + MaybeSync + 'a,
 {
     fn call(&self) -> Parser<'a, Output> {
         (self)()
@@ -46,6 +48,7 @@ impl<'a, Output> LazyParser<'a, Output> {
     }
 }
 
+#[cfg(not(feature = "sync"))]
 pub fn lazy<'a, F, Output>(f: F) -> Parser<'a, Output>
 where
     Output: 'a,
@@ -55,10 +58,35 @@ where
 
     let lazy = move |state: &mut ParserState<'a>| {
         let parser = unsafe { &mut *cell.get() }.get();
+        state.enter()?;
         // Bypass flag dispatch — the cached inner parser never has flags set
         // (flags live on the outer wrapper). This avoids a branch on every
         // recursive call through lazy().
-        parser.parser_fn.call(state)
+        let result = parser.parser_fn.call(state);
+        state.leave();
+        result
+    };
+
+    Parser::new(lazy)
+}
+
+/// Thread-safe `lazy`: the parser is built at most once, even when first
+/// called from several threads.
+#[cfg(feature = "sync")]
+pub fn lazy<'a, F, Output>(f: F) -> Parser<'a, Output>
+where
+    Output: 'a,
+    F: LazyParserFn<'a, Output> + 'a,
+{
+    let cell: std::sync::OnceLock<Parser<'a, Output>> = std::sync::OnceLock::new();
+
+    let lazy = move |state: &mut ParserState<'a>| {
+        let parser = cell.get_or_init(|| f.call());
+        state.enter()?;
+        // As above, the cached inner parser never has flags set
+        let result = parser.parser_fn.call(state);
+        state.leave();
+        result
     };
 
     Parser::new(lazy)
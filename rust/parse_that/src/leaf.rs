@@ -3,31 +3,65 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
 
 use crate::parse::Parser;
+use crate::shared::MaybeSync;
 use crate::state::{ParserState, Span};
 
 use aho_corasick::{AhoCorasickBuilder, Anchored, Input, MatchKind, StartKind};
 
 /// Global regex cache — avoids recompiling the same pattern on repeated parser construction.
 pub fn cached_regex(pattern: &str) -> Arc<Regex> {
-    static CACHE: OnceLock<Mutex<HashMap<String, Arc<Regex>>>> = OnceLock::new();
-    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
-    let mut map = cache.lock().unwrap();
+    static CACHE: RegexCache<Regex> = OnceLock::new();
+    cached(&CACHE, pattern, |pattern| Regex::new(pattern).ok())
+}
+
+/// A regex matched only at the start of the remaining input, as `regex()`,
+/// `regex_span()` and `sp_regex()` do: a failing match returns at the first
+/// byte that rules it out instead of scanning ahead for a later match.
+pub type AnchoredRegex = regex_automata::meta::Regex;
+
+/// Global cache of [`AnchoredRegex`]es, like `cached_regex`.
+pub fn cached_anchored_regex(pattern: &str) -> Arc<AnchoredRegex> {
+    static CACHE: RegexCache<AnchoredRegex> = OnceLock::new();
+    cached(&CACHE, pattern, |pattern| AnchoredRegex::new(pattern).ok())
+}
+
+/// Match `re` at the start of `haystack`, returning the match length.
+#[inline(always)]
+pub fn anchored_match(re: &AnchoredRegex, haystack: &str) -> Option<usize> {
+    let input = regex_automata::Input::new(haystack).anchored(regex_automata::Anchored::Yes);
+    re.search(&input).map(|m| m.end())
+}
+
+type RegexCache<R> = OnceLock<Mutex<HashMap<String, Arc<R>>>>;
+
+fn cached<R>(
+    cache: &'static RegexCache<R>,
+    pattern: &str,
+    compile: impl FnOnce(&str) -> Option<R>,
+) -> Arc<R> {
+    let mut map = cache.get_or_init(Default::default).lock().unwrap();
     if let Some(re) = map.get(pattern) {
         return Arc::clone(re);
     }
     let re = Arc::new(
-        Regex::new(pattern).unwrap_or_else(|_| panic!("Failed to compile regex: {}", pattern)),
+        compile(pattern).unwrap_or_else(|| panic!("Failed to compile regex: {}", pattern)),
     );
     map.insert(pattern.to_owned(), Arc::clone(&re));
     re
 }
 
+/// Length of the whitespace at the current offset: ASCII space, tab, CR and
+/// LF, or the state's `Trivia` if it has one.
 #[inline(always)]
 pub fn trim_leading_whitespace(state: &ParserState<'_>) -> usize {
     let bytes = state.src_bytes;
     let mut i = state.offset;
     let end = bytes.len();
 
+    if let Some(trivia) = &state.trivia {
+        return trivia.skip(state.src, i);
+    }
+
     // Fast path: first byte is not whitespace (most common case)
     if i >= end || !matches!(unsafe { *bytes.get_unchecked(i) }, b' ' | b'\t' | b'\n' | b'\r') {
         return 0;
@@ -136,25 +170,17 @@ pub fn string_span<'a>(s: &'a str) -> Parser<'a, Span<'a>> {
 }
 
 #[inline(always)]
-fn regex_impl<'a>(re: &Regex, state: &mut ParserState<'a>) -> Option<Span<'a>> {
-    let slc = state.src.get(state.offset..)?;
-    match re.find_at(slc, 0) {
-        Some(m) => {
-            if m.start() != 0 {
-                return None;
-            }
-            let start = state.offset;
-            state.offset += m.end();
-            Some(Span::new(start, state.offset, state.src))
-        }
-        None => None,
-    }
+fn regex_impl<'a>(re: &AnchoredRegex, state: &mut ParserState<'a>) -> Option<Span<'a>> {
+    let len = anchored_match(re, state.src.get(state.offset..)?)?;
+    let start = state.offset;
+    state.offset += len;
+    Some(Span::new(start, state.offset, state.src))
 }
 
 #[inline(always)]
 #[allow(clippy::manual_map)]
 pub fn regex<'a>(r: &'a str) -> Parser<'a, &'a str> {
-    let re = cached_regex(r);
+    let re = cached_anchored_regex(r);
     #[cfg(feature = "diagnostics")]
     let label: &'static str = Box::leak(format!("/{}/", r).into_boxed_str());
     let regex = move |state: &mut ParserState<'a>| match regex_impl(&re, state) {
@@ -171,7 +197,7 @@ pub fn regex<'a>(r: &'a str) -> Parser<'a, &'a str> {
 #[inline(always)]
 #[allow(clippy::manual_map)]
 pub fn regex_span<'a>(r: &'a str) -> Parser<'a, Span<'a>> {
-    let re = cached_regex(r);
+    let re = cached_anchored_regex(r);
     #[cfg(feature = "diagnostics")]
     let label: &'static str = Box::leak(format!("/{}/", r).into_boxed_str());
     let regex = move |state: &mut ParserState<'a>| match regex_impl(&re, state) {
@@ -188,7 +214,7 @@ pub fn regex_span<'a>(r: &'a str) -> Parser<'a, Span<'a>> {
 #[inline]
 pub fn take_while_span<'a, F>(f: F) -> Parser<'a, Span<'a>>
 where
-    F: Fn(char) -> bool + 'a,
+    F: Fn(char) -> bool + MaybeSync + 'a,
 {
     let take_while = move |state: &mut ParserState<'a>| {
         let slc = state.src.get(state.offset..)?;
@@ -370,15 +396,21 @@ pub fn any_span<'a>(patterns: &[&'a str]) -> Parser<'a, Span<'a>> {
 // ── one_of: flat N-way alternation ────────────────────────────
 
 /// Flat N-way alternation — tries each parser in order with checkpoint backtracking.
+/// A failed branch rewinds the offset and the user context. Stops at a `cut()` failure.
 pub fn one_of<'a, O: 'a>(parsers: Vec<Parser<'a, O>>) -> Parser<'a, O> {
     Parser::new(move |state: &mut ParserState<'a>| {
+        let checkpoint = state.offset;
+        let context = state.context.clone();
         for parser in &parsers {
-            let checkpoint = state.offset;
             if let Some(value) = parser.call(state) {
                 return Some(value);
             }
             state.furthest_offset = state.furthest_offset.max(state.offset);
             state.offset = checkpoint;
+            state.restore_context(&context);
+            if state.is_cut() {
+                return None;
+            }
         }
         None
     })
@@ -1,4 +1,3 @@
-#![feature(cold_path)]
 #![feature(portable_simd)]
 
 pub mod parse;
@@ -30,3 +29,12 @@ pub use split::*;
 
 pub mod state;
 pub use state::*;
+
+pub mod line_index;
+pub use line_index::*;
+
+pub mod shared;
+pub use shared::*;
+
+pub mod trivia;
+pub use trivia::*;
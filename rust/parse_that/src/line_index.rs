@@ -0,0 +1,160 @@
+use std::cell::OnceCell;
+use std::ops::Range;
+use std::sync::Arc;
+
+// ── Line index ────────────────────────────────────────────────
+//
+// Offsets to line/column positions (and back) by binary search over line
+// starts found once with memchr. Non-ASCII characters are recorded so that
+// char and UTF-16 columns (editors, LSP) need only the characters on the
+// line, not a rescan of it; all-ASCII sources record none.
+
+/// A position in the source. `line` is 1-based, columns are 0-based.
+#[derive(Debug, Default, PartialEq, Clone, Copy, Hash, Eq)]
+pub struct LineCol {
+    pub line: usize,
+    /// Column in bytes.
+    pub col: usize,
+    /// Column in Unicode scalar values.
+    pub char_col: usize,
+    /// Column in UTF-16 code units.
+    pub utf16_col: usize,
+}
+
+/// Line starts of a source, for O(log n) offset ↔ line/column mapping.
+/// Lines are separated by `\n`; a `\r` before it belongs to the line.
+#[derive(Debug, Default, PartialEq, Clone, Hash, Eq)]
+pub struct LineIndex {
+    /// Offset of the first byte of each line; the first is always 0.
+    line_starts: Vec<usize>,
+    /// Offset and UTF-8 length of each non-ASCII char, in source order.
+    wide_chars: Vec<(usize, u8)>,
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn new(src: &str) -> LineIndex {
+        let bytes = src.as_bytes();
+        let mut line_starts = vec![0];
+        line_starts.extend(memchr::memchr_iter(b'\n', bytes).map(|nl| nl + 1));
+        let wide_chars = if src.is_ascii() {
+            Vec::new()
+        } else {
+            src.char_indices()
+                .filter(|(_, c)| !c.is_ascii())
+                .map(|(i, c)| (i, c.len_utf8() as u8))
+                .collect()
+        };
+        LineIndex {
+            line_starts,
+            wide_chars,
+            len: src.len(),
+        }
+    }
+
+    /// Number of lines; a trailing `\n` starts an (empty) last line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Byte range of 1-based `line`, excluding its `\n`.
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self.line_starts.get(line).map_or(self.len, |next| next - 1);
+        Some(start..end)
+    }
+
+    /// Position of `offset` (clamped to the source length), which should be
+    /// on a char boundary.
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let start = self.line_starts[line - 1];
+        let col = offset - start;
+        let (mut char_col, mut utf16_col) = (col, col);
+        for &(_, len) in self.wide_chars_in(start..offset) {
+            char_col -= len as usize - 1;
+            utf16_col -= len as usize - utf16_len(len);
+        }
+        LineCol {
+            line,
+            col,
+            char_col,
+            utf16_col,
+        }
+    }
+
+    /// Offset of byte column `col` on 1-based `line`; `None` past its end.
+    pub fn offset(&self, line: usize, col: usize) -> Option<usize> {
+        let range = self.line_range(line)?;
+        (range.start + col <= range.end).then_some(range.start + col)
+    }
+
+    /// Offset of char column `char_col` on 1-based `line`.
+    pub fn offset_char(&self, line: usize, char_col: usize) -> Option<usize> {
+        self.offset_by(line, char_col, |_| 1)
+    }
+
+    /// Offset of UTF-16 column `utf16_col` on 1-based `line`; `None` past its
+    /// end or between the halves of a surrogate pair.
+    pub fn offset_utf16(&self, line: usize, utf16_col: usize) -> Option<usize> {
+        self.offset_by(line, utf16_col, utf16_len)
+    }
+
+    fn wide_chars_in(&self, range: Range<usize>) -> &[(usize, u8)] {
+        let lo = self.wide_chars.partition_point(|&(i, _)| i < range.start);
+        let hi = self.wide_chars.partition_point(|&(i, _)| i < range.end);
+        &self.wide_chars[lo..hi]
+    }
+
+    /// Walk `line` by `units` per char (ASCII is always one unit).
+    fn offset_by(&self, line: usize, col: usize, units: fn(u8) -> usize) -> Option<usize> {
+        let range = self.line_range(line)?;
+        let (mut offset, mut remaining) = (range.start, col);
+        for &(i, len) in self.wide_chars_in(range.clone()) {
+            let ascii = i - offset;
+            if remaining <= ascii {
+                return Some(offset + remaining);
+            }
+            remaining -= ascii;
+            offset = i;
+            remaining = remaining.checked_sub(units(len))?;
+            offset += len as usize;
+        }
+        (offset + remaining <= range.end).then_some(offset + remaining)
+    }
+}
+
+#[inline]
+fn utf16_len(utf8_len: u8) -> usize {
+    if utf8_len == 4 { 2 } else { 1 }
+}
+
+/// A `LineIndex` built on first use, shared by clones. It is derived from the
+/// source, so equality and hashing ignore it.
+#[derive(Debug, Default, Clone)]
+pub struct LazyLineIndex(OnceCell<Arc<LineIndex>>);
+
+impl LazyLineIndex {
+    pub fn get_or_init(&self, src: &str) -> &LineIndex {
+        self.0.get_or_init(|| Arc::new(LineIndex::new(src)))
+    }
+}
+
+impl From<Arc<LineIndex>> for LazyLineIndex {
+    fn from(index: Arc<LineIndex>) -> Self {
+        LazyLineIndex(OnceCell::from(index))
+    }
+}
+
+impl PartialEq for LazyLineIndex {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for LazyLineIndex {}
+
+impl std::hash::Hash for LazyLineIndex {
+    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
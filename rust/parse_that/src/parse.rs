@@ -1,7 +1,8 @@
 use smallbox::{SmallBox, space::S32};
 
 use crate::leaf::trim_leading_whitespace;
-use crate::state::ParserState;
+use crate::shared::MaybeSync;
+use crate::state::{CustomError, LimitError, ParserState};
 
 /// Structured error returned by `Parser::parse_or_error()` on failure.
 #[derive(Debug, Clone)]
@@ -18,6 +19,14 @@ pub struct ParseError {
     /// Parser names/descriptions that were expected at the failure point.
     /// Populated from parser context when available.
     pub expected: Vec<String>,
+    /// Set when the failure is a committed one (see `Parser::cut`): the offset
+    /// where the cut parser failed. `offset`, `line` and `column` point here.
+    pub cut_offset: Option<usize>,
+    /// The user error that caused the failure (see `Parser::try_map`).
+    pub custom_error: Option<CustomError>,
+    /// Set when a resource limit stopped the parse (see `Parser::max_depth`,
+    /// `Parser::fuel`) rather than the input failing to match.
+    pub limit_error: Option<LimitError>,
 }
 
 impl std::fmt::Display for ParseError {
@@ -30,21 +39,35 @@ impl std::fmt::Display for ParseError {
         if !self.expected.is_empty() {
             write!(f, ", expected: {}", self.expected.join(" | "))?;
         }
+        if let Some(error) = &self.custom_error {
+            write!(f, ": {error}")?;
+        }
+        if let Some(error) = &self.limit_error {
+            write!(f, ": {error}")?;
+        }
         Ok(())
     }
 }
 
-impl std::error::Error for ParseError {}
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        if let Some(error) = &self.limit_error {
+            return Some(error);
+        }
+        let error = self.custom_error.as_ref()?;
+        Some(&*error.error)
+    }
+}
 
 pub type ParserResult<'a, Output> = Option<Output>;
 
-pub trait ParserFn<'a, Output>: 'a {
+pub trait ParserFn<'a, Output>: MaybeSync + 'a {
     fn call(&self, state: &mut ParserState<'a>) -> ParserResult<'a, Output>;
 }
 
 impl<'a, Output, F> ParserFn<'a, Output> for F
 where
-    F: Fn(&mut ParserState<'a>) -> ParserResult<'a, Output> + 'a,
+    F: Fn(&mut ParserState<'a>) -> ParserResult<'a, Output> + MaybeSync + 'a,
 {
     #[inline]
     fn call(&self, state: &mut ParserState<'a>) -> ParserResult<'a, Output> {
@@ -52,6 +75,18 @@ where
     }
 }
 
+/// Identity function that fixes a closure's argument type to
+/// `&mut ParserState`, for macro bodies that touch `state` before calling a
+/// parser (e.g. `alt!`).
+#[doc(hidden)]
+#[inline(always)]
+pub fn __parser_fn<'a, O, F>(f: F) -> F
+where
+    F: Fn(&mut ParserState<'a>) -> Option<O>,
+{
+    f
+}
+
 // ── Parser flags ──────────────────────────────────────────────
 
 const FLAG_TRIM_WS: u8 = 0b0001;
@@ -77,21 +112,37 @@ where
     }
 
     /// Core call method — inlines flag behavior to avoid wrapper boxing.
+    ///
+    /// A cut (see `Parser::cut`) only lives while its failure propagates: a
+    /// parser that succeeds has swallowed it, so any `cut_offset` left behind
+    /// is cleared here, with its `limit_error`, rather than making a later
+    /// alternation refuse to backtrack.
     #[inline(always)]
     pub fn call(&self, state: &mut ParserState<'a>) -> Option<Output> {
         if self.flags == 0 {
-            return self.parser_fn.call(state);
+            let result = self.parser_fn.call(state);
+            if result.is_some() && state.is_cut() {
+                state.clear_failure();
+            }
+            return result;
         }
         // Fast path: trim_ws only (most common flag combination)
         if self.flags == FLAG_TRIM_WS {
             state.offset += trim_leading_whitespace(state);
             let result = self.parser_fn.call(state);
             if result.is_some() {
+                if state.is_cut() {
+                    state.clear_failure();
+                }
                 state.offset += trim_leading_whitespace(state);
             }
             return result;
         }
-        self.call_with_flags_cold(state)
+        let result = self.call_with_flags_cold(state);
+        if result.is_some() && state.is_cut() {
+            state.clear_failure();
+        }
+        result
     }
 
     #[inline(never)]
@@ -101,22 +152,24 @@ where
             state.offset += trim_leading_whitespace(state);
         }
 
-        // Pre: save state for backtracking
+        // Pre: save state for backtracking (offset and user context)
         let checkpoint = if self.flags & FLAG_SAVE_STATE != 0 {
-            Some(state.offset)
+            Some((state.offset, state.context.clone()))
         } else {
             None
         };
 
         let result = self.parser_fn.call(state);
 
-        // Post: handle save_state backtracking
-        if let Some(cp) = checkpoint {
-            if result.is_none() {
-                state.furthest_offset = state.furthest_offset.max(state.offset);
-                state.offset = cp;
-                return None;
-            }
+        // Post: handle save_state backtracking (a cut failure still rewinds
+        // the offset; the cut location is kept in `cut_offset`)
+        if let Some((cp, context)) = checkpoint
+            && result.is_none()
+        {
+            state.furthest_offset = state.furthest_offset.max(state.offset);
+            state.offset = cp;
+            state.restore_context(&context);
+            return None;
         }
 
         // Post: trim whitespace — skip on failure
@@ -154,8 +207,26 @@ where
         self.parse_return_state(src).0
     }
 
+    /// Parse with a user context (see `Parser::with_state`), returning it
+    /// alongside the result.
+    ///
+    /// # Panics
+    ///
+    /// If the parse replaced the context with one of another type.
+    pub fn parse_with_context<T>(&self, src: &'a str, context: T) -> (Option<Output>, T)
+    where
+        T: std::any::Any + Clone,
+    {
+        let mut state = ParserState::with_context(src, context);
+        let result = self.call(&mut state);
+        let context = state
+            .take_context()
+            .expect("parse_with_context: context type changed during the parse");
+        (result, context)
+    }
+
     pub fn parse_or_error(&self, src: &'a str) -> Result<Output, ParseError> {
-        let (result, state) = self.parse_return_state(src);
+        let (result, mut state) = self.parse_return_state(src);
         match result {
             Some(value) => Ok(value),
             None => {
@@ -164,18 +235,29 @@ where
                 #[cfg(not(feature = "diagnostics"))]
                 let expected = Vec::new();
 
+                let custom_error = state.current_custom_error();
+                if let Some(cut) = state.cut_offset {
+                    state.offset = cut;
+                }
                 Err(ParseError {
                     offset: state.offset,
                     furthest_offset: state.furthest_offset,
                     line: state.get_line_number(),
                     column: state.get_column_number(),
                     expected,
+                    cut_offset: state.cut_offset,
+                    custom_error,
+                    limit_error: state.limit_error,
                 })
             }
         }
     }
 
-    /// Mark this parser to save/restore state on failure (checkpoint-based).
+    /// Mark this parser to save/restore state on failure (checkpoint-based):
+    /// the offset, and the user context if one is set. Alternations already
+    /// do this for their branches; use it where `opt`, `many` and the like
+    /// backtrack over a parser that updates the context. The checkpoint shares
+    /// the context rather than copying it (see `UserContext`).
     #[inline]
     pub fn save_state(mut self) -> Parser<'a, Output> {
         self.flags |= FLAG_SAVE_STATE;
@@ -229,11 +229,7 @@ fn parse_media_query<'a>(state: &mut ParserState<'a>) -> Option<MediaQuery<'a>>
         // No media type — must be a condition
         state.offset = cp;
         modifier = None;
-        if let Some(cond) = parse_media_condition(state) {
-            conditions.push(cond);
-        } else {
-            return None;
-        }
+        conditions.push(parse_media_condition(state)?);
     }
 
     Some(MediaQuery {
@@ -20,7 +20,7 @@ use scan::*;
 use selector::*;
 use value::*;
 
-use crate::lazy::lazy;
+use crate::combinators::recursive;
 use crate::parse::*;
 use crate::span_parser::*;
 use crate::state::{ParserState, Span};
@@ -28,181 +28,184 @@ use smallvec::SmallVec;
 
 // ── At-rules ────────────────────────────────────────────────
 
-fn css_at_rule<'a>() -> Parser<'a, CssNode<'a>> {
-    lazy(|| {
-        let rule = css_rule();
-        let decl_block = css_declaration_block();
-        let kf_block = css_keyframe_block();
-        let ws = css_ws();
-        let ident = css_ident();
-        let at_sign = sp_string("@");
-        let open_brace = sp_string("{");
-        let close_brace = sp_string("}");
-        let semi = sp_string(";");
-        let skip_to_semi_brace = sp_take_until_any(b";}");
-        let skip_to_close = sp_take_until_any(b"}");
-        let kf_name_parser = css_ident().or(css_string());
+fn css_at_rule<'a>(rule: Parser<'a, CssNode<'a>>) -> Parser<'a, CssNode<'a>> {
+    let decl_block = css_declaration_block();
+    let kf_block = css_keyframe_block();
+    let ws = css_ws();
+    let ident = css_ident();
+    let at_sign = sp_string("@");
+    let open_brace = sp_string("{");
+    let close_brace = sp_string("}");
+    let semi = sp_string(";");
+    let skip_to_semi_brace = sp_take_until_any(b";}");
+    let skip_to_close = sp_take_until_any(b"}");
+    let kf_name_parser = css_ident().or(css_string());
 
-        Parser::new(move |state: &mut ParserState<'a>| {
-            at_sign.call(state)?;
-            let name = ident.call(state)?;
-            ws.call(state);
-
-            // First-byte dispatch on at-rule name for O(1) branching
-            let name_first_byte = state.src_bytes.get(name.start).copied().unwrap_or(0);
-            match name_first_byte {
-                b'm' if name.as_str() == "media" => {
-                    let queries = parse_media_query_list(state);
-                    ws.call(state);
-                    open_brace.call(state)?;
+    Parser::new(move |state: &mut ParserState<'a>| {
+        at_sign.call(state)?;
+        let name = ident.call(state)?;
+        ws.call(state);
 
-                    let mut body = Vec::with_capacity(4);
-                    loop {
-                        ws.call(state);
-                        if close_brace.call(state).is_some() {
-                            break;
-                        }
-                        if state.is_at_end() {
-                            return None;
-                        }
-                        if let Some(node) = rule.call(state) {
-                            body.push(node);
-                        } else {
-                            skip_to_semi_brace.call(state);
-                            semi.call(state);
-                        }
-                    }
+        // First-byte dispatch on at-rule name for O(1) branching
+        let name_first_byte = state.src_bytes.get(name.start).copied().unwrap_or(0);
+        match name_first_byte {
+            b'm' if name.as_str() == "media" => {
+                let queries = parse_media_query_list(state);
+                ws.call(state);
+                open_brace.call(state)?;
 
-                    Some(CssNode::AtMedia {
-                        queries,
-                        body,
-                    })
-                }
-                b's' if name.as_str() == "supports" => {
-                    let condition = parse_supports_condition(state);
+                let mut body = Vec::with_capacity(4);
+                loop {
                     ws.call(state);
-                    open_brace.call(state)?;
-
-                    let mut body = Vec::with_capacity(4);
-                    loop {
-                        ws.call(state);
-                        if close_brace.call(state).is_some() {
-                            break;
-                        }
-                        if state.is_at_end() {
-                            return None;
-                        }
-                        if let Some(node) = rule.call(state) {
-                            body.push(node);
-                        } else {
-                            skip_to_semi_brace.call(state);
-                            semi.call(state);
-                        }
+                    if close_brace.call(state).is_some() {
+                        break;
+                    }
+                    if state.is_at_end() {
+                        return None;
+                    }
+                    if let Some(node) = rule.call(state) {
+                        body.push(node);
+                    } else if state.limit_error.is_some() {
+                        return None;
+                    } else {
+                        skip_to_semi_brace.call(state);
+                        semi.call(state);
                     }
+                }
 
-                    let condition = condition.unwrap_or(SupportsCondition::Declaration {
-                        property: Span::new(state.offset, state.offset, state.src),
-                        value: Vec::new(),
-                    });
+                Some(CssNode::AtMedia {
+                    queries,
+                    body,
+                })
+            }
+            b's' if name.as_str() == "supports" => {
+                let condition = parse_supports_condition(state);
+                ws.call(state);
+                open_brace.call(state)?;
 
-                    Some(CssNode::AtSupports {
-                        condition,
-                        body,
-                    })
-                }
-                b'f' if name.as_str() == "font-face" => {
+                let mut body = Vec::with_capacity(4);
+                loop {
                     ws.call(state);
-                    let declarations = decl_block.call(state)?;
-                    Some(CssNode::AtFontFace { declarations })
+                    if close_brace.call(state).is_some() {
+                        break;
+                    }
+                    if state.is_at_end() {
+                        return None;
+                    }
+                    if let Some(node) = rule.call(state) {
+                        body.push(node);
+                    } else if state.limit_error.is_some() {
+                        return None;
+                    } else {
+                        skip_to_semi_brace.call(state);
+                        semi.call(state);
+                    }
                 }
-                b'i' if name.as_str() == "import" => {
+
+                let condition = condition.unwrap_or(SupportsCondition::Declaration {
+                    property: Span::new(state.offset, state.offset, state.src),
+                    value: Vec::new(),
+                });
+
+                Some(CssNode::AtSupports {
+                    condition,
+                    body,
+                })
+            }
+            b'f' if name.as_str() == "font-face" => {
+                ws.call(state);
+                let declarations = decl_block.call(state)?;
+                Some(CssNode::AtFontFace { declarations })
+            }
+            b'i' if name.as_str() == "import" => {
+                ws.call(state);
+                let mut values: SmallVec<[CssValue<'a>; 4]> = SmallVec::new();
+                loop {
                     ws.call(state);
-                    let mut values: SmallVec<[CssValue<'a>; 4]> = SmallVec::new();
-                    loop {
-                        ws.call(state);
-                        if semi.call(state).is_some() || state.is_at_end() {
-                            break;
-                        }
-                        if let Some(v) = parse_value_inline(state) {
-                            values.push(v);
-                        } else {
-                            break;
-                        }
+                    if semi.call(state).is_some() || state.is_at_end() {
+                        break;
+                    }
+                    if let Some(v) = parse_value_inline(state) {
+                        values.push(v);
+                    } else {
+                        break;
                     }
-                    Some(CssNode::AtImport { values })
                 }
-                b'k' | b'-' if matches!(name.as_str(), "keyframes" | "-webkit-keyframes" | "-moz-keyframes") => {
-                    ws.call(state);
-                    let kf_name = kf_name_parser.call(state)?;
+                Some(CssNode::AtImport { values })
+            }
+            b'k' | b'-' if matches!(name.as_str(), "keyframes" | "-webkit-keyframes" | "-moz-keyframes") => {
+                ws.call(state);
+                let kf_name = kf_name_parser.call(state)?;
+                ws.call(state);
+                open_brace.call(state)?;
+
+                let mut blocks: SmallVec<[KeyframeBlock<'_>; 8]> = SmallVec::new();
+                loop {
                     ws.call(state);
-                    open_brace.call(state)?;
+                    if close_brace.call(state).is_some() {
+                        break;
+                    }
+                    if state.is_at_end() {
+                        return None;
+                    }
+                    if let Some(block) = kf_block.call(state) {
+                        blocks.push(block);
+                    } else {
+                        skip_to_close.call(state);
+                    }
+                }
 
-                    let mut blocks: SmallVec<[KeyframeBlock<'_>; 8]> = SmallVec::new();
+                Some(CssNode::AtKeyframes {
+                    name: kf_name,
+                    blocks,
+                })
+            }
+            _ => {
+                let skip = sp_take_until_any(b"{;");
+                let prelude_span = skip.call(state).unwrap_or(Span::new(
+                    state.offset,
+                    state.offset,
+                    state.src,
+                ));
+
+                let has_block = if open_brace.call(state).is_some() {
+                    true
+                } else {
+                    semi.call(state);
+                    false
+                };
+
+                let body = if has_block {
+                    let mut rules = Vec::with_capacity(4);
                     loop {
                         ws.call(state);
                         if close_brace.call(state).is_some() {
                             break;
                         }
                         if state.is_at_end() {
-                            return None;
+                            break;
                         }
-                        if let Some(block) = kf_block.call(state) {
-                            blocks.push(block);
+                        if let Some(node) = rule.call(state) {
+                            rules.push(node);
+                        } else if state.limit_error.is_some() {
+                            return None;
                         } else {
-                            skip_to_close.call(state);
+                            skip_to_semi_brace.call(state);
+                            semi.call(state);
                         }
                     }
-
-                    Some(CssNode::AtKeyframes {
-                        name: kf_name,
-                        blocks,
-                    })
-                }
-                _ => {
-                    let skip = sp_take_until_any(b"{;");
-                    let prelude_span = skip.call(state).unwrap_or(Span::new(
-                        state.offset,
-                        state.offset,
-                        state.src,
-                    ));
-
-                    let has_block = if open_brace.call(state).is_some() {
-                        true
-                    } else {
-                        semi.call(state);
-                        false
-                    };
-
-                    let body = if has_block {
-                        let mut rules = Vec::with_capacity(4);
-                        loop {
-                            ws.call(state);
-                            if close_brace.call(state).is_some() {
-                                break;
-                            }
-                            if state.is_at_end() {
-                                break;
-                            }
-                            if let Some(node) = rule.call(state) {
-                                rules.push(node);
-                            } else {
-                                skip_to_semi_brace.call(state);
-                                semi.call(state);
-                            }
-                        }
-                        Some(rules)
-                    } else {
-                        None
-                    };
-
-                    Some(CssNode::GenericAtRule {
-                        name,
-                        prelude: prelude_span,
-                        body,
-                    })
-                }
+                    Some(rules)
+                } else {
+                    None
+                };
+
+                Some(CssNode::GenericAtRule {
+                    name,
+                    prelude: prelude_span,
+                    body,
+                })
             }
-        })
+        }
     })
 }
 
@@ -228,8 +231,8 @@ fn css_qualified_rule<'a>() -> Parser<'a, CssNode<'a>> {
 // ── Top-level rule ──────────────────────────────────────────
 
 fn css_rule<'a>() -> Parser<'a, CssNode<'a>> {
-    lazy(|| {
-        let at_rule = css_at_rule();
+    recursive(|rule| {
+        let at_rule = css_at_rule(rule.parser());
         let qualified_rule = css_qualified_rule();
         let comment = css_comment();
 
@@ -280,6 +283,9 @@ pub fn css_stylesheet<'a>() -> Parser<'a, NodeVec<'a>> {
             }
             if let Some(node) = rule.call(state) {
                 nodes.push(node);
+            } else if state.limit_error.is_some() {
+                // A depth or fuel limit is not recovered from
+                return None;
             } else {
                 // Skip to next rule to recover
                 if state.is_at_end() {
@@ -304,3 +310,21 @@ pub fn css_stylesheet<'a>() -> Parser<'a, NodeVec<'a>> {
 pub fn css_parser<'a>() -> Parser<'a, NodeVec<'a>> {
     css_stylesheet()
 }
+
+/// `css_parser()` as a [`Grammar`](crate::shared::Grammar), to build once and
+/// share as a `SyncParser<CssGrammar>`.
+#[cfg(feature = "sync")]
+pub struct CssGrammar;
+
+// The CSS parser keeps nothing between parses but its sub-parsers.
+#[cfg(feature = "sync")]
+impl crate::shared::sealed::Sealed for CssGrammar {}
+
+#[cfg(feature = "sync")]
+impl crate::shared::Grammar for CssGrammar {
+    type Output<'a> = NodeVec<'a>;
+
+    fn build<'a>() -> Parser<'a, NodeVec<'a>> {
+        css_parser()
+    }
+}
@@ -137,24 +137,19 @@ pub(crate) fn css_string_fast<'a>(state: &mut ParserState<'a>) -> Option<Span<'a
 
     let mut i = start + 1;
     loop {
-        // SIMD scan for quote or backslash
-        match memchr::memchr2(quote, b'\\', bytes.get(i..)?) {
-            None => return None, // unterminated string
-            Some(pos) => {
-                i += pos;
-                if unsafe { *bytes.get_unchecked(i) } == quote {
-                    i += 1; // consume closing quote
-                    state.offset = i;
-                    return Some(Span::new(start, i, state.src));
-                }
-                // backslash: skip next byte (CSS escapes are simpler than JSON)
-                i += 1;
-                if i >= bytes.len() {
-                    return None;
-                }
-                i += 1; // skip the escaped character
-            }
+        // SIMD scan for quote or backslash — None means unterminated string
+        i += memchr::memchr2(quote, b'\\', bytes.get(i..)?)?;
+        if unsafe { *bytes.get_unchecked(i) } == quote {
+            i += 1; // consume closing quote
+            state.offset = i;
+            return Some(Span::new(start, i, state.src));
         }
+        // backslash: skip next byte (CSS escapes are simpler than JSON)
+        i += 1;
+        if i >= bytes.len() {
+            return None;
+        }
+        i += 1; // skip the escaped character
     }
 }
 
@@ -176,16 +171,12 @@ pub(crate) fn css_block_comment_fast<'a>(state: &mut ParserState<'a>) -> Option<
 
     let mut i = start + 2;
     loop {
-        match memchr::memchr(b'*', bytes.get(i..)?) {
-            None => return None, // unterminated comment
-            Some(pos) => {
-                i += pos + 1;
-                if i < len && unsafe { *bytes.get_unchecked(i) } == b'/' {
-                    i += 1;
-                    state.offset = i;
-                    return Some(Span::new(start, i, state.src));
-                }
-            }
+        // None means unterminated comment
+        i += memchr::memchr(b'*', bytes.get(i..)?)? + 1;
+        if i < len && unsafe { *bytes.get_unchecked(i) } == b'/' {
+            i += 1;
+            state.offset = i;
+            return Some(Span::new(start, i, state.src));
         }
     }
 }
@@ -2,28 +2,34 @@
 
 use super::scan::*;
 use super::types::*;
-use crate::lazy::lazy;
+use crate::combinators::recursive;
 use crate::parse::*;
 use crate::span_parser::*;
 use crate::state::{ParserState, Span};
 
 pub(super) fn css_selector_list<'a>() -> Parser<'a, SelectorVec<'a>> {
-    let sel = css_complex_selector();
-    let ws = css_ws();
-    let comma_sp = sp_string(",");
-    let comma = Parser::new(move |state: &mut ParserState<'a>| {
-        ws.call(state);
-        let v = comma_sp.call(state)?;
-        ws.call(state);
-        Some(v)
-    });
-    sel.sep_by_small::<_, [CssSelector<'a>; 2]>(comma, 1..)
+    recursive(|list| {
+        let sel = css_complex_selector(list.parser());
+        let ws = css_ws();
+        let comma_sp = sp_string(",");
+        let comma = Parser::new(move |state: &mut ParserState<'a>| {
+            ws.call(state);
+            let v = comma_sp.call(state)?;
+            ws.call(state);
+            Some(v)
+        });
+        sel.sep_by_small::<_, [CssSelector<'a>; 2]>(comma, 1..)
+    })
 }
 
-pub(super) fn css_complex_selector<'a>() -> Parser<'a, CssSelector<'a>> {
-    lazy(|| {
-        let compound = css_compound_selector();
-        let recurse = css_complex_selector();
+/// Selectors joined by combinators; `selector_list` parses the arguments of
+/// functional pseudo-classes such as `:not()`.
+pub(super) fn css_complex_selector<'a>(
+    selector_list: Parser<'a, SelectorVec<'a>>,
+) -> Parser<'a, CssSelector<'a>> {
+    recursive(|complex| {
+        let compound = css_compound_selector(selector_list);
+        let recurse = complex.parser();
         let ws = css_ws();
 
         Parser::new(move |state: &mut ParserState<'a>| {
@@ -78,9 +84,11 @@ pub(super) fn css_complex_selector<'a>() -> Parser<'a, CssSelector<'a>> {
     })
 }
 
-pub(super) fn css_compound_selector<'a>() -> Parser<'a, CssSelector<'a>> {
+pub(super) fn css_compound_selector<'a>(
+    selector_list: Parser<'a, SelectorVec<'a>>,
+) -> Parser<'a, CssSelector<'a>> {
     let attr_sel = css_attribute_selector();
-    let pseudo_sel = css_pseudo_selector();
+    let pseudo_sel = css_pseudo_selector(selector_list);
 
     Parser::new(move |state: &mut ParserState<'a>| {
         let mut parts: CompoundVec<'_> = Vec::with_capacity(3);
@@ -204,110 +212,109 @@ pub(super) fn css_attribute_selector<'a>() -> Parser<'a, CssSelector<'a>> {
     })
 }
 
-pub(super) fn css_pseudo_selector<'a>() -> Parser<'a, CssSelector<'a>> {
-    lazy(|| {
-        let double_colon = sp_string("::");
-        let single_colon = sp_string(":");
-        let ident = css_ident();
-        let open_paren = sp_string("(");
-        let close_paren = sp_string(")");
-        let ws = css_ws();
-        // Inline An+B parser — replaces regex
-        let anb = Parser::new(|state: &mut ParserState<'a>| {
-            let bytes = state.src_bytes;
-            let start = state.offset;
-            let len = bytes.len();
-            let mut i = start;
+pub(super) fn css_pseudo_selector<'a>(
+    selector_list: Parser<'a, SelectorVec<'a>>,
+) -> Parser<'a, CssSelector<'a>> {
+    let double_colon = sp_string("::");
+    let single_colon = sp_string(":");
+    let ident = css_ident();
+    let open_paren = sp_string("(");
+    let close_paren = sp_string(")");
+    let ws = css_ws();
+    // Inline An+B parser — replaces regex
+    let anb = Parser::new(|state: &mut ParserState<'a>| {
+        let bytes = state.src_bytes;
+        let start = state.offset;
+        let len = bytes.len();
+        let mut i = start;
 
-            if i >= len {
-                return None;
-            }
+        if i >= len {
+            return None;
+        }
 
-            // Check for "even" / "odd" keywords
-            if i + 4 <= len && &bytes[i..i + 4] == b"even" {
-                state.offset = i + 4;
-                return Some(Span::new(start, i + 4, state.src));
-            }
-            if i + 3 <= len && &bytes[i..i + 3] == b"odd" {
-                state.offset = i + 3;
-                return Some(Span::new(start, i + 3, state.src));
-            }
+        // Check for "even" / "odd" keywords
+        if i + 4 <= len && &bytes[i..i + 4] == b"even" {
+            state.offset = i + 4;
+            return Some(Span::new(start, i + 4, state.src));
+        }
+        if i + 3 <= len && &bytes[i..i + 3] == b"odd" {
+            state.offset = i + 3;
+            return Some(Span::new(start, i + 3, state.src));
+        }
 
-            // Optional sign
-            if i < len && matches!(bytes[i], b'+' | b'-') {
-                i += 1;
-            }
+        // Optional sign
+        if i < len && matches!(bytes[i], b'+' | b'-') {
+            i += 1;
+        }
 
-            // Try An+B or plain integer
-            let digit_start = i;
-            while i < len && bytes[i].is_ascii_digit() {
+        // Try An+B or plain integer
+        let digit_start = i;
+        while i < len && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        if i < len && bytes[i] == b'n' {
+            // An+B form: [+-]?\d*n\s*([+-]\s*\d+)?
+            i += 1; // skip 'n'
+            // Skip whitespace
+            while i < len && matches!(bytes[i], b' ' | b'\t') {
                 i += 1;
             }
-
-            if i < len && bytes[i] == b'n' {
-                // An+B form: [+-]?\d*n\s*([+-]\s*\d+)?
-                i += 1; // skip 'n'
-                // Skip whitespace
+            // Optional [+-]\s*\d+
+            if i < len && matches!(bytes[i], b'+' | b'-') {
+                i += 1;
                 while i < len && matches!(bytes[i], b' ' | b'\t') {
                     i += 1;
                 }
-                // Optional [+-]\s*\d+
-                if i < len && matches!(bytes[i], b'+' | b'-') {
+                while i < len && bytes[i].is_ascii_digit() {
                     i += 1;
-                    while i < len && matches!(bytes[i], b' ' | b'\t') {
-                        i += 1;
-                    }
-                    while i < len && bytes[i].is_ascii_digit() {
-                        i += 1;
-                    }
                 }
-                state.offset = i;
-                Some(Span::new(start, i, state.src))
-            } else if i > digit_start {
-                // Plain integer: [+-]?\d+
-                state.offset = i;
-                Some(Span::new(start, i, state.src))
-            } else {
-                None
-            }
-        });
-        let sel_list = css_selector_list();
-
-        Parser::new(move |state: &mut ParserState<'a>| {
-            let is_element = double_colon.call(state).is_some();
-            if !is_element && single_colon.call(state).is_none() {
-                return None;
             }
+            state.offset = i;
+            Some(Span::new(start, i, state.src))
+        } else if i > digit_start {
+            // Plain integer: [+-]?\d+
+            state.offset = i;
+            Some(Span::new(start, i, state.src))
+        } else {
+            None
+        }
+    });
 
-            let name = ident.call(state)?;
+    Parser::new(move |state: &mut ParserState<'a>| {
+        let is_element = double_colon.call(state).is_some();
+        if !is_element && single_colon.call(state).is_none() {
+            return None;
+        }
 
-            if open_paren.call(state).is_some() {
-                ws.call(state);
+        let name = ident.call(state)?;
 
-                let name_str = name.as_str();
-                if name_str.starts_with("nth-") {
-                    ws.call(state);
-                    let arg = if let Some(s) = anb.call(state) {
-                        vec![CssSelector::Type(s)]
-                    } else {
-                        Vec::new()
-                    };
-                    ws.call(state);
-                    close_paren.call(state)?;
-                    return Some(CssSelector::PseudoFunction { name, args: arg });
-                }
+        if open_paren.call(state).is_some() {
+            ws.call(state);
 
-                let args: Vec<CssSelector<'_>> = sel_list.call(state).map(|v| v.into_vec()).unwrap_or_default();
+            let name_str = name.as_str();
+            if name_str.starts_with("nth-") {
+                ws.call(state);
+                let arg = if let Some(s) = anb.call(state) {
+                    vec![CssSelector::Type(s)]
+                } else {
+                    Vec::new()
+                };
                 ws.call(state);
                 close_paren.call(state)?;
-                return Some(CssSelector::PseudoFunction { name, args });
+                return Some(CssSelector::PseudoFunction { name, args: arg });
             }
 
-            if is_element {
-                Some(CssSelector::PseudoElement(name))
-            } else {
-                Some(CssSelector::PseudoClass(name))
-            }
-        })
+            let args: Vec<CssSelector<'_>> = selector_list.call(state).map(|v| v.into_vec()).unwrap_or_default();
+            ws.call(state);
+            close_paren.call(state)?;
+            return Some(CssSelector::PseudoFunction { name, args });
+        }
+
+        if is_element {
+            Some(CssSelector::PseudoElement(name))
+        } else {
+            Some(CssSelector::PseudoClass(name))
+        }
     })
 }
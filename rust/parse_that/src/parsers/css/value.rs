@@ -80,11 +80,11 @@ pub(super) fn parse_value_inline<'a>(state: &mut ParserState<'a>) -> Option<CssV
             let cp = state.offset;
             state.offset += 1;
             css_ws_comment_fast(state);
-            if let Some(s) = css_ident_fast(state) {
-                if s.as_str() == "important" {
-                    // We'll mark important in the declaration, skip this value
-                    return Some(CssValue::Ident(s)); // Let caller detect
-                }
+            if let Some(s) = css_ident_fast(state)
+                && s.as_str() == "important"
+            {
+                // We'll mark important in the declaration, skip this value
+                return Some(CssValue::Ident(s)); // Let caller detect
             }
             state.offset = cp;
             None
@@ -115,7 +115,7 @@ pub(crate) fn number_span_fast<'a>(state: &mut ParserState<'a>) -> Option<Span<'
 }
 
 #[inline(always)]
-fn parse_json_number_f64(span: Span<'_>, is_integer: bool) -> f64 {
+pub(crate) fn parse_json_number_f64(span: Span<'_>, is_integer: bool) -> f64 {
     let s = span.as_str();
     let bytes = s.as_bytes();
     if !is_integer {
@@ -141,7 +141,7 @@ fn parse_json_number_f64(span: Span<'_>, is_integer: bool) -> f64 {
 
 
 #[inline(always)]
-fn decode_hex_nibble(b: u8) -> Option<u16> {
+pub(crate) fn decode_hex_nibble(b: u8) -> Option<u16> {
     match b {
         b'0'..=b'9' => Some((b - b'0') as u16),
         b'a'..=b'f' => Some((b - b'a' + 10) as u16),
@@ -151,7 +151,7 @@ fn decode_hex_nibble(b: u8) -> Option<u16> {
 }
 
 #[inline(always)]
-fn decode_hex4(bytes: &[u8], start: usize) -> Option<u16> {
+pub(crate) fn decode_hex4(bytes: &[u8], start: usize) -> Option<u16> {
     Some(
         (decode_hex_nibble(*bytes.get(start)?)? << 12)
             | (decode_hex_nibble(*bytes.get(start + 1)?)? << 8)
@@ -162,70 +162,72 @@ fn decode_hex4(bytes: &[u8], start: usize) -> Option<u16> {
 
 // ── Monolithic JSON string scanner ────────────────────────────
 
+/// Validates the escape sequence whose `\` sits at `i - 1` and returns the
+/// offset just past it. Rejects unknown escapes, truncated `\uXXXX`, and
+/// unpaired surrogates.
+#[inline(always)]
+fn skip_json_escape(bytes: &[u8], mut i: usize) -> Option<usize> {
+    match *bytes.get(i)? {
+        b'u' => {
+            if i + 4 >= bytes.len() {
+                return None;
+            }
+            // Check for surrogate pairs: \uD800-\uDBFF must be followed by \uDC00-\uDFFF.
+            let hi = decode_hex4(bytes, i + 1)?;
+            i += 5; // skip u + 4 hex digits
+            if (0xD800..=0xDBFF).contains(&hi) {
+                // High surrogate — must be followed by \uDC00-\uDFFF
+                if i + 5 < bytes.len() && bytes[i] == b'\\' && bytes[i + 1] == b'u' {
+                    let lo = decode_hex4(bytes, i + 2)?;
+                    if !(0xDC00..=0xDFFF).contains(&lo) {
+                        return None; // not a valid low surrogate
+                    }
+                    i += 6; // skip \uXXXX for the low surrogate
+                } else {
+                    return None; // lone high surrogate
+                }
+            } else if (0xDC00..=0xDFFF).contains(&hi) {
+                return None; // lone low surrogate
+            }
+            Some(i)
+        }
+        b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => Some(i + 1),
+        _ => None, // invalid escape sequence
+    }
+}
+
 /// Core JSON string scanner with configurable span bounds.
 /// When `include_quotes` is false, returns content between quotes (exclusive).
 /// When `include_quotes` is true, returns full span including delimiters.
+/// The flag is `true` when the string contains at least one escape sequence.
 #[inline(always)]
-fn json_string_fast_inner<'a>(
+pub(crate) fn json_string_fast_inner<'a>(
     state: &mut ParserState<'a>,
     include_quotes: bool,
-) -> Option<Span<'a>> {
+) -> Option<(Span<'a>, bool)> {
     let bytes = state.src_bytes;
     let start = state.offset;
     if bytes.get(start) != Some(&b'"') {
         return None;
     }
     let mut i = start + 1;
+    let mut has_escapes = false;
     loop {
-        // SIMD scan for next '"' or '\\'
-        match memchr::memchr2(b'"', b'\\', bytes.get(i..)?) {
-            None => return None, // unterminated string
-            Some(pos) => {
-                i += pos;
-                if unsafe { *bytes.get_unchecked(i) } == b'"' {
-                    i += 1; // consume closing quote
-                    state.offset = i;
-                    return if include_quotes {
-                        Some(Span::new(start, i, state.src))
-                    } else {
-                        Some(Span::new(start + 1, i - 1, state.src))
-                    };
-                }
-                // backslash: skip escape sequence
-                i += 1;
-                if i >= bytes.len() {
-                    return None;
-                }
-                match unsafe { *bytes.get_unchecked(i) } {
-                    b'u' => {
-                        if i + 4 >= bytes.len() {
-                            return None;
-                        }
-                        // Check for surrogate pairs: \uD800-\uDBFF must be followed by \uDC00-\uDFFF.
-                        let hi = decode_hex4(bytes, i + 1)?;
-                        i += 5; // skip u + 4 hex digits
-                        if (0xD800..=0xDBFF).contains(&hi) {
-                            // High surrogate — must be followed by \uDC00-\uDFFF
-                            if i + 5 < bytes.len() && bytes[i] == b'\\' && bytes[i + 1] == b'u' {
-                                let lo = decode_hex4(bytes, i + 2)?;
-                                if !(0xDC00..=0xDFFF).contains(&lo) {
-                                    return None; // not a valid low surrogate
-                                }
-                                i += 6; // skip \uXXXX for the low surrogate
-                            } else {
-                                return None; // lone high surrogate
-                            }
-                        } else if (0xDC00..=0xDFFF).contains(&hi) {
-                            return None; // lone low surrogate
-                        }
-                    }
-                    b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => {
-                        i += 1;
-                    }
-                    _ => return None, // invalid escape sequence
-                }
-            }
+        // SIMD scan for next '"' or '\\' — None means unterminated string
+        i += memchr::memchr2(b'"', b'\\', bytes.get(i..)?)?;
+        if unsafe { *bytes.get_unchecked(i) } == b'"' {
+            i += 1; // consume closing quote
+            state.offset = i;
+            let span = if include_quotes {
+                Span::new(start, i, state.src)
+            } else {
+                Span::new(start + 1, i - 1, state.src)
+            };
+            return Some((span, has_escapes));
         }
+        // backslash: skip escape sequence
+        has_escapes = true;
+        i = skip_json_escape(bytes, i + 1)?;
     }
 }
 
@@ -233,14 +235,77 @@ fn json_string_fast_inner<'a>(
 /// Returns the span of the *content* (between the quotes, exclusive of `"`).
 #[inline(always)]
 pub(crate) fn json_string_fast<'a>(state: &mut ParserState<'a>) -> Option<Span<'a>> {
-    json_string_fast_inner(state, false)
+    json_string_fast_inner(state, false).map(|(span, _)| span)
 }
 
 /// Scans a JSON string `"..."` with `\`-escape handling using SIMD (memchr2).
 /// Returns the span including the quote delimiters (matches regex behavior).
 #[inline(always)]
 pub(crate) fn json_string_fast_quoted<'a>(state: &mut ParserState<'a>) -> Option<Span<'a>> {
-    json_string_fast_inner(state, true)
+    json_string_fast_inner(state, true).map(|(span, _)| span)
+}
+
+/// Scans a JSON string and decodes its escape sequences.
+/// Strings without a backslash borrow straight from the source (zero-copy);
+/// escaped strings are decoded into an owned buffer.
+#[inline(always)]
+pub(crate) fn json_string_cow<'a>(state: &mut ParserState<'a>) -> Option<Cow<'a, str>> {
+    let (span, has_escapes) = json_string_fast_inner(state, false)?;
+    if !has_escapes {
+        return Some(Cow::Borrowed(span.as_str()));
+    }
+    unescape_json_string(span.as_str()).map(Cow::Owned)
+}
+
+/// Decodes the escape sequences of a raw JSON string body (quotes excluded).
+/// Surrogate pairs are combined into a single scalar value. Returns `None` on
+/// an invalid escape, truncated `\uXXXX`, or unpaired surrogate.
+#[cold]
+#[inline(never)]
+pub(crate) fn unescape_json_string(raw: &str) -> Option<String> {
+    let bytes = raw.as_bytes();
+    let mut out = String::with_capacity(bytes.len());
+    let mut i = 0;
+    while let Some(pos) = memchr::memchr(b'\\', &bytes[i..]) {
+        // Copy the unescaped run verbatim — `\` is ASCII, so both ends are
+        // char boundaries.
+        out.push_str(&raw[i..i + pos]);
+        i += pos + 1;
+        let c = match *bytes.get(i)? {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{08}',
+            b'f' => '\u{0C}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let hi = decode_hex4(bytes, i + 1)?;
+                i += 4;
+                let cp = if (0xD800..=0xDBFF).contains(&hi) {
+                    if bytes.get(i + 1) != Some(&b'\\') || bytes.get(i + 2) != Some(&b'u') {
+                        return None; // lone high surrogate
+                    }
+                    let lo = decode_hex4(bytes, i + 3)?;
+                    if !(0xDC00..=0xDFFF).contains(&lo) {
+                        return None;
+                    }
+                    i += 6;
+                    0x10000 + (((hi as u32) - 0xD800) << 10) + ((lo as u32) - 0xDC00)
+                } else {
+                    hi as u32
+                };
+                // Lone low surrogates are rejected here (not a valid scalar).
+                char::from_u32(cp)?
+            }
+            _ => return None, // invalid escape sequence
+        };
+        out.push(c);
+        i += 1;
+    }
+    out.push_str(&raw[i..]);
+    Some(out)
 }
 
 // ── Utility: number_span_fast as a standalone Parser ──────────
@@ -264,33 +329,145 @@ pub enum JsonValue<'a> {
     Object(Box<Vec<(Cow<'a, str>, JsonValue<'a>)>>),
 }
 
+/// Decode one RFC 6901 reference token (`~1` → `/`, `~0` → `~`).
+fn pointer_token(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// RFC 6901 array index: `0` or a digit run without leading zeros.
+fn pointer_index(token: &str) -> Option<usize> {
+    let bytes = token.as_bytes();
+    if bytes.is_empty()
+        || !bytes.iter().all(u8::is_ascii_digit)
+        || (bytes.len() > 1 && bytes[0] == b'0')
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+impl<'a> JsonValue<'a> {
+    /// Look up a value by RFC 6901 JSON Pointer, e.g. `"/a/0/b"`.
+    /// The empty pointer refers to `self`. Object keys match the first
+    /// occurrence when duplicates exist.
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue<'a>> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .try_fold(self, |node, token| match node {
+                JsonValue::Object(entries) => {
+                    let key = pointer_token(token);
+                    entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+                }
+                JsonValue::Array(items) => items.get(pointer_index(token)?),
+                _ => None,
+            })
+    }
+
+    /// Mutable variant of [`JsonValue::pointer`].
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonValue<'a>> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .try_fold(self, |node, token| match node {
+                JsonValue::Object(entries) => {
+                    let key = pointer_token(token);
+                    entries.iter_mut().find(|(k, _)| *k == key).map(|(_, v)| v)
+                }
+                JsonValue::Array(items) => items.get_mut(pointer_index(token)?),
+                _ => None,
+            })
+    }
+}
+
 pub fn json_value<'a>() -> Parser<'a, JsonValue<'a>> {
-    // ── String parser using monolithic SIMD scanner ────────────
-    // Returns raw spans (no unescape) — zero-copy.
+    crate::recursive(json_value_with)
+}
+
+/// A value tree the JSON grammar can build, so that `json_value()` and
+/// `json_value_lossless()` share one grammar and differ only in how a
+/// scanned number is kept.
+pub(crate) trait JsonTree<'a>: Sized + 'a {
+    fn null() -> Self;
+    fn bool(b: bool) -> Self;
+    fn number(ns: NumberSpan<'a>) -> Self;
+    fn string(s: Cow<'a, str>) -> Self;
+    fn array(items: Vec<Self>) -> Self;
+    fn object(entries: Vec<(Cow<'a, str>, Self)>) -> Self;
+}
 
-    let json_string_content =
-        || -> Parser<'a, Cow<'a, str>> { sp_json_string().map(|s| Cow::Borrowed(s.as_str())) };
+impl<'a> JsonTree<'a> for JsonValue<'a> {
+    fn null() -> Self {
+        JsonValue::Null
+    }
 
-    // ── Leaf values ───────────────────────────────────────────
+    fn bool(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+
+    #[inline]
+    fn number(ns: NumberSpan<'a>) -> Self {
+        JsonValue::Number(parse_json_number_f64(ns.span, ns.is_integer))
+    }
 
-    let json_null: Parser<'a, JsonValue<'a>> = sp_string("null").map(|_| JsonValue::Null);
-    let json_true: Parser<'a, JsonValue<'a>> = sp_string("true").map(|_| JsonValue::Bool(true));
-    let json_false: Parser<'a, JsonValue<'a>> = sp_string("false").map(|_| JsonValue::Bool(false));
+    fn string(s: Cow<'a, str>) -> Self {
+        JsonValue::String(s)
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        JsonValue::Array(Box::new(items))
+    }
 
-    let json_number = || -> Parser<'a, JsonValue<'a>> {
+    fn object(entries: Vec<(Cow<'a, str>, Self)>) -> Self {
+        JsonValue::Object(Box::new(entries))
+    }
+}
+
+/// The JSON grammar, with nested values parsed by `value`.
+pub(crate) fn json_value_with<'a, V: JsonTree<'a>>(
+    value: crate::Recursive<'a, V>,
+) -> Parser<'a, V> {
+    // ── String parser using monolithic SIMD scanner ────────────
+    // Unescaped strings borrow from the source; escapes decode to owned.
+
+    let json_string_content = || -> Parser<'a, Cow<'a, str>> {
         Parser::new(move |state: &mut ParserState<'a>| {
-            let ns = number_span_fast_ex(state)?;
-            Some(JsonValue::Number(parse_json_number_f64(ns.span, ns.is_integer)))
+            let result = json_string_cow(state);
+            #[cfg(feature = "diagnostics")]
+            if result.is_none() {
+                state.add_expected("string");
+            }
+            result
         })
     };
 
-    let json_string =
-        || -> Parser<'a, JsonValue<'a>> { json_string_content().map(JsonValue::String) };
+    // ── Leaf values ───────────────────────────────────────────
+
+    let json_null = sp_string("null").map(|_| V::null());
+    let json_true = sp_string("true").map(|_| V::bool(true));
+    let json_false = sp_string("false").map(|_| V::bool(false));
+
+    let json_number = Parser::new(move |state: &mut ParserState<'a>| {
+        let ns = number_span_fast_ex(state)?;
+        Some(V::number(ns))
+    });
+
+    let json_string = json_string_content().map(V::string);
 
     // ── Array: hand-rolled loop inside a Parser for pre-allocated capacity ──
 
-    let json_array = crate::lazy::lazy(|| {
-        let value = json_value();
+    let json_array = {
+        let value = value.parser();
         let open = sp_string("[");
         let close = sp_string("]");
         let comma = sp_string(",").trim_whitespace();
@@ -300,7 +477,7 @@ pub fn json_value<'a>() -> Parser<'a, JsonValue<'a>> {
             crate::leaf::trim_leading_whitespace_mut(state);
 
             if close.call(state).is_some() {
-                return Some(JsonValue::Array(Box::new(Vec::new())));
+                return Some(V::array(Vec::new()));
             }
 
             let mut items = Vec::with_capacity(4);
@@ -314,14 +491,14 @@ pub fn json_value<'a>() -> Parser<'a, JsonValue<'a>> {
             }
 
             close.call(state)?;
-            Some(JsonValue::Array(Box::new(items)))
+            Some(V::array(items))
         })
-    });
+    };
 
     // ── Object: hand-rolled loop inside a Parser for pre-allocated capacity ──
 
-    let json_object = crate::lazy::lazy(move || {
-        let value = json_value();
+    let json_object = {
+        let value = value.parser();
         let key = json_string_content();
         let open = sp_string("{");
         let close = sp_string("}");
@@ -333,7 +510,7 @@ pub fn json_value<'a>() -> Parser<'a, JsonValue<'a>> {
             crate::leaf::trim_leading_whitespace_mut(state);
 
             if close.call(state).is_some() {
-                return Some(JsonValue::Object(Box::new(Vec::new())));
+                return Some(V::object(Vec::new()));
             }
 
             let mut entries = Vec::with_capacity(4);
@@ -350,23 +527,341 @@ pub fn json_value<'a>() -> Parser<'a, JsonValue<'a>> {
             }
 
             close.call(state)?;
-            Some(JsonValue::Object(Box::new(entries)))
+            Some(V::object(entries))
         })
-    });
+    };
 
     // ── First-byte dispatch ───────────────────────────────────
 
     crate::leaf::dispatch_byte_multi(vec![
         (b"{" as &[u8], json_object),
         (b"[", json_array),
-        (b"\"", json_string()),
+        (b"\"", json_string),
         (b"t", json_true),
         (b"f", json_false),
         (b"n", json_null),
-        (b"-0123456789", json_number()),
+        (b"-0123456789", json_number),
     ])
 }
 
 pub fn json_parser<'a>() -> Parser<'a, JsonValue<'a>> {
     json_value().trim_whitespace()
 }
+
+/// `json_parser()` as a [`Grammar`](crate::shared::Grammar), to build once
+/// and share as a `SyncParser<JsonGrammar>`.
+#[cfg(feature = "sync")]
+pub struct JsonGrammar;
+
+// The JSON parser keeps nothing between parses but its sub-parsers.
+#[cfg(feature = "sync")]
+impl crate::shared::sealed::Sealed for JsonGrammar {}
+
+#[cfg(feature = "sync")]
+impl crate::shared::Grammar for JsonGrammar {
+    type Output<'a> = JsonValue<'a>;
+
+    fn build<'a>() -> Parser<'a, JsonValue<'a>> {
+        json_parser()
+    }
+}
+
+// ── Strict RFC 8259 parser ────────────────────────────────────
+
+/// Default nesting limit for `json_parser_strict()` (same as serde_json).
+pub const JSON_STRICT_MAX_DEPTH: usize = 128;
+
+/// Fail at `offset` with a diagnostics label pinned to that exact position.
+#[cold]
+#[inline(never)]
+fn strict_fail<T>(state: &mut ParserState<'_>, offset: usize, _label: &'static str) -> Option<T> {
+    state.offset = offset;
+    #[cfg(feature = "diagnostics")]
+    {
+        state.add_expected(_label);
+        state.furthest_offset = state.furthest_offset.max(offset);
+    }
+    None
+}
+
+/// RFC 8259 number: `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`.
+/// Unlike `number_span_fast_ex`, never stops short on `007`, `1.` or `1e`.
+#[inline(always)]
+fn number_span_strict<'a>(state: &mut ParserState<'a>) -> Option<NumberSpan<'a>> {
+    let bytes = state.src_bytes;
+    let start = state.offset;
+    let is_digit = |i: usize| matches!(bytes.get(i), Some(b'0'..=b'9'));
+    let mut i = start;
+    let mut is_integer = true;
+
+    if bytes.get(i) == Some(&b'-') {
+        i += 1;
+    }
+    match bytes.get(i) {
+        Some(b'0') => {
+            i += 1;
+            if is_digit(i) {
+                return strict_fail(state, i, "number without leading zeros");
+            }
+        }
+        Some(b'1'..=b'9') => {
+            while is_digit(i) {
+                i += 1;
+            }
+        }
+        _ => return strict_fail(state, i, "digit"),
+    }
+
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+        if !is_digit(i) {
+            return strict_fail(state, i, "digit after decimal point");
+        }
+        while is_digit(i) {
+            i += 1;
+        }
+        is_integer = false;
+    }
+
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        i += 1;
+        if matches!(bytes.get(i), Some(b'+' | b'-')) {
+            i += 1;
+        }
+        if !is_digit(i) {
+            return strict_fail(state, i, "digit in exponent");
+        }
+        while is_digit(i) {
+            i += 1;
+        }
+        is_integer = false;
+    }
+
+    state.offset = i;
+    Some(NumberSpan {
+        span: Span::new(start, i, state.src),
+        is_integer,
+    })
+}
+
+/// Find the next `"`, `\`, or raw control character (< 0x20) at or after `i`.
+#[inline(always)]
+pub(crate) fn find_string_special(bytes: &[u8], mut i: usize) -> Option<usize> {
+    let end = bytes.len();
+    {
+        use std::simd::prelude::*;
+        while i + 16 <= end {
+            let chunk = u8x16::from_slice(&bytes[i..i + 16]);
+            let mask = chunk.simd_eq(u8x16::splat(b'"'))
+                | chunk.simd_eq(u8x16::splat(b'\\'))
+                | chunk.simd_lt(u8x16::splat(0x20));
+            if mask.any() {
+                return Some(i + mask.to_bitmask().trailing_zeros() as usize);
+            }
+            i += 16;
+        }
+    }
+    while i < end {
+        match unsafe { *bytes.get_unchecked(i) } {
+            b'"' | b'\\' | 0x00..=0x1F => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// RFC 8259 string: like `json_string_cow`, but raw control characters
+/// (U+0000–U+001F) must be escaped.
+#[inline(always)]
+fn json_string_strict<'a>(state: &mut ParserState<'a>) -> Option<Cow<'a, str>> {
+    let bytes = state.src_bytes;
+    let start = state.offset;
+    if bytes.get(start) != Some(&b'"') {
+        return strict_fail(state, start, "string");
+    }
+    let mut i = start + 1;
+    let mut has_escapes = false;
+    loop {
+        let Some(pos) = find_string_special(bytes, i) else {
+            return strict_fail(state, bytes.len(), "closing quote");
+        };
+        i = pos;
+        match unsafe { *bytes.get_unchecked(i) } {
+            b'"' => break,
+            b'\\' => {
+                has_escapes = true;
+                match skip_json_escape(bytes, i + 1) {
+                    Some(next) => i = next,
+                    None => return strict_fail(state, i, "valid escape sequence"),
+                }
+            }
+            _ => return strict_fail(state, i, "escaped control character"),
+        }
+    }
+    state.offset = i + 1;
+    let raw = Span::new(start + 1, i, state.src).as_str();
+    if !has_escapes {
+        return Some(Cow::Borrowed(raw));
+    }
+    unescape_json_string(raw).map(Cow::Owned)
+}
+
+/// Skip whitespace and report whether the next byte is `expected`.
+#[inline(always)]
+fn strict_peek(state: &mut ParserState<'_>, expected: u8) -> bool {
+    crate::leaf::trim_leading_whitespace_mut(state);
+    state.src_bytes.get(state.offset) == Some(&expected)
+}
+
+/// Strict array body, from the `[`.
+fn json_array_strict<'a>(state: &mut ParserState<'a>) -> Option<JsonValue<'a>> {
+    state.offset += 1;
+    let mut items = Vec::new();
+    if !strict_peek(state, b']') {
+        loop {
+            items.push(json_value_strict(state)?);
+            if strict_peek(state, b']') {
+                break;
+            }
+            if state.src_bytes.get(state.offset) != Some(&b',') {
+                return strict_fail(state, state.offset, "`,` or `]`");
+            }
+            state.offset += 1;
+            crate::leaf::trim_leading_whitespace_mut(state);
+        }
+    }
+    state.offset += 1;
+    Some(JsonValue::Array(Box::new(items)))
+}
+
+/// Strict object body, from the `{`.
+fn json_object_strict<'a>(state: &mut ParserState<'a>) -> Option<JsonValue<'a>> {
+    state.offset += 1;
+    let mut entries = Vec::new();
+    if !strict_peek(state, b'}') {
+        loop {
+            let key = json_string_strict(state)?;
+            if !strict_peek(state, b':') {
+                return strict_fail(state, state.offset, "`:`");
+            }
+            state.offset += 1;
+            crate::leaf::trim_leading_whitespace_mut(state);
+            entries.push((key, json_value_strict(state)?));
+            if strict_peek(state, b'}') {
+                break;
+            }
+            if state.src_bytes.get(state.offset) != Some(&b',') {
+                return strict_fail(state, state.offset, "`,` or `}`");
+            }
+            state.offset += 1;
+            crate::leaf::trim_leading_whitespace_mut(state);
+        }
+    }
+    state.offset += 1;
+    Some(JsonValue::Object(Box::new(entries)))
+}
+
+/// Strict value scanner. Arrays and objects go through `state.enter()`, so
+/// the nesting limit is the state's `max_depth`; recursion is a plain function
+/// call, so each level costs one small stack frame.
+fn json_value_strict<'a>(state: &mut ParserState<'a>) -> Option<JsonValue<'a>> {
+    let Some(&b) = state.src_bytes.get(state.offset) else {
+        return strict_fail(state, state.offset, "value");
+    };
+    match b {
+        b'[' | b'{' => {
+            state.enter()?;
+            let value = if b == b'[' {
+                json_array_strict(state)
+            } else {
+                json_object_strict(state)
+            };
+            state.leave();
+            value
+        }
+        b'"' => json_string_strict(state).map(JsonValue::String),
+        b'-' | b'0'..=b'9' => {
+            let ns = number_span_strict(state)?;
+            Some(JsonValue::Number(parse_json_number_f64(ns.span, ns.is_integer)))
+        }
+        b't' if state.src_bytes[state.offset..].starts_with(b"true") => {
+            state.offset += 4;
+            Some(JsonValue::Bool(true))
+        }
+        b'f' if state.src_bytes[state.offset..].starts_with(b"false") => {
+            state.offset += 5;
+            Some(JsonValue::Bool(false))
+        }
+        b'n' if state.src_bytes[state.offset..].starts_with(b"null") => {
+            state.offset += 4;
+            Some(JsonValue::Null)
+        }
+        _ => strict_fail(state, state.offset, "value"),
+    }
+}
+
+/// RFC 8259-conformant JSON parser. Compared to `json_parser()` it rejects
+/// raw control characters in strings, leading zeros and dangling `.`/`e` in
+/// numbers, trailing content after the value, and nesting deeper than
+/// [`JSON_STRICT_MAX_DEPTH`]. Failures carry precise diagnostics labels, and
+/// too deep nesting reports `LimitError::Depth`.
+pub fn json_parser_strict<'a>() -> Parser<'a, JsonValue<'a>> {
+    json_parser_strict_with_depth(JSON_STRICT_MAX_DEPTH)
+}
+
+/// `json_parser_strict()` with a custom nesting limit; deeper input fails
+/// with `LimitError::Depth` (see `Parser::max_depth`).
+pub fn json_parser_strict_with_depth<'a>(max_depth: usize) -> Parser<'a, JsonValue<'a>> {
+    Parser::new(json_value_strict)
+        .trim_whitespace()
+        .eof()
+        .max_depth(max_depth)
+}
+
+// ── JSON Lines (NDJSON) ───────────────────────────────────────
+
+/// Newline-delimited JSON: one strict value per line, blank lines skipped.
+/// Records are parsed independently, so a malformed line yields an `Err`
+/// carrying its own line/column and expected labels while parsing carries on
+/// with the next line.
+#[cfg(feature = "diagnostics")]
+pub fn json_lines_parser<'a>() -> Parser<'a, Vec<Result<JsonValue<'a>, crate::state::Diagnostic>>> {
+    Parser::new(|state: &mut ParserState<'a>| {
+        let src = state.src;
+        let mut records = Vec::new();
+        let mut start = state.offset;
+        while start < src.len() {
+            let end =
+                memchr::memchr(b'\n', &src.as_bytes()[start..]).map_or(src.len(), |i| start + i);
+            // Bounding the source at the newline keeps a bad record from
+            // reading into the next one; line numbers still count from the top.
+            let mut line = ParserState {
+                offset: start,
+                max_depth: Some(JSON_STRICT_MAX_DEPTH),
+                ..ParserState::new(&src[..end])
+            };
+            crate::leaf::trim_leading_whitespace_mut(&mut line);
+            if line.offset < end {
+                let value =
+                    json_value_strict(&mut line).and_then(|value| {
+                        crate::leaf::trim_leading_whitespace_mut(&mut line);
+                        let at = line.offset;
+                        if at < end {
+                            return strict_fail(&mut line, at, "end of line");
+                        }
+                        Some(value)
+                    });
+                records.push(value.ok_or_else(|| {
+                    // Share the parse's index: one built per line would
+                    // rescan the file from the top for every bad record.
+                    state.line_index();
+                    line.line_index = state.line_index.clone();
+                    line.snapshot_diagnostic(start)
+                }));
+            }
+            start = end + 1;
+        }
+        state.offset = src.len();
+        Some(records)
+    })
+}
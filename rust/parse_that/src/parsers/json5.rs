@@ -0,0 +1,520 @@
+// JSON5 / JSONC dialect parser. Produces the same `JsonValue` as `json_value()`.
+//
+// Extensions over JSON: `//` and `/* */` comments, trailing commas, unquoted
+// (identifier) keys, single-quoted strings with line continuations and extra
+// escapes, hex numbers, leading/trailing decimal points, explicit `+`, and
+// `Infinity`/`NaN`. JSONC (comments + trailing commas) is a strict subset.
+
+use std::borrow::Cow;
+
+use crate::parse::*;
+use crate::parsers::json::{JsonValue, decode_hex_nibble, decode_hex4};
+use crate::span_parser::*;
+use crate::state::{ParserState, Span};
+
+use pprint::Pretty;
+
+// ── Whitespace + comment scanner ──────────────────────────────
+
+/// Length of the non-ASCII JSON5 whitespace or line terminator at `i`
+/// (U+00A0, U+FEFF, U+2028, U+2029, and the other `Zs` characters).
+#[inline]
+fn unicode_ws_len(bytes: &[u8], i: usize) -> Option<usize> {
+    match *bytes.get(i..i + 2)? {
+        [0xC2, 0xA0] => Some(2),
+        [0xE1 | 0xE2 | 0xE3 | 0xEF, _] => {
+            let s = std::str::from_utf8(bytes.get(i..i + 3)?).ok()?;
+            let c = s.chars().next()?;
+            (c == '\u{FEFF}' || (c.is_whitespace() && c != '\u{85}')).then_some(3)
+        }
+        _ => None,
+    }
+}
+
+/// Scan a single comment: `//...` up to (not including) the line terminator,
+/// or `/* ... */`. Returns the span including delimiters; None if no comment
+/// starts here or a block comment is unterminated.
+pub(crate) fn json5_comment_fast<'a>(state: &mut ParserState<'a>) -> Option<Span<'a>> {
+    let bytes = state.src_bytes;
+    let start = state.offset;
+    if bytes.get(start) != Some(&b'/') {
+        return None;
+    }
+    let mut i = start + 2;
+    match *bytes.get(start + 1)? {
+        b'/' => loop {
+            // Line terminators: LF, CR, U+2028, U+2029 (E2 80 A8/A9)
+            match memchr::memchr3(b'\n', b'\r', 0xE2, bytes.get(i..)?) {
+                None => {
+                    i = bytes.len();
+                    break;
+                }
+                Some(pos) => {
+                    i += pos;
+                    if bytes[i] != 0xE2
+                        || matches!(bytes.get(i + 1..i + 3), Some([0x80, 0xA8 | 0xA9]))
+                    {
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+        },
+        b'*' => loop {
+            // None means unterminated comment
+            i += memchr::memchr(b'*', bytes.get(i..)?)? + 1;
+            if bytes.get(i) == Some(&b'/') {
+                i += 1;
+                break;
+            }
+        },
+        _ => return None,
+    }
+    state.offset = i;
+    Some(Span::new(start, i, state.src))
+}
+
+/// Skip JSON5 whitespace and comments. Fails only on an unterminated block
+/// comment.
+#[inline]
+fn skip_trivia(state: &mut ParserState<'_>) -> Option<()> {
+    let bytes = state.src_bytes;
+    loop {
+        let i = state.offset;
+        match bytes.get(i) {
+            Some(b' ' | b'\t' | b'\n' | b'\r' | 0x0B | 0x0C) => state.offset += 1,
+            Some(b'/') => {
+                json5_comment_fast(state)?;
+            }
+            Some(0x80..) => match unicode_ws_len(bytes, i) {
+                Some(n) => state.offset += n,
+                None => return Some(()),
+            },
+            _ => return Some(()),
+        }
+    }
+}
+
+/// Scan JSON5 whitespace and comments. Returns the skipped span; empty if
+/// there is nothing to skip.
+pub(crate) fn json5_ws_comment_fast<'a>(state: &mut ParserState<'a>) -> Option<Span<'a>> {
+    let start = state.offset;
+    skip_trivia(state)?;
+    Some(Span::new(start, state.offset, state.src))
+}
+
+// ── String scanner ────────────────────────────────────────────
+
+/// Core JSON5 string scanner: `"..."` or `'...'`. Returns the content span
+/// (exclusive of quotes) and whether it contains escapes. Raw LF/CR are
+/// rejected; escaped ones are line continuations.
+#[inline(always)]
+fn json5_string_inner<'a>(state: &mut ParserState<'a>) -> Option<(Span<'a>, bool)> {
+    let bytes = state.src_bytes;
+    let start = state.offset;
+    let quote = *bytes.get(start)?;
+    if quote != b'"' && quote != b'\'' {
+        return None;
+    }
+    let mut i = start + 1;
+    let mut has_escapes = false;
+    loop {
+        match *bytes.get(i)? {
+            b if b == quote => break,
+            b'\n' | b'\r' => return None,
+            b'\\' => {
+                has_escapes = true;
+                i += 1;
+                match *bytes.get(i)? {
+                    b'1'..=b'9' => return None,
+                    b'0' if bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => return None,
+                    b'x' => {
+                        decode_hex_nibble(*bytes.get(i + 1)?)?;
+                        decode_hex_nibble(*bytes.get(i + 2)?)?;
+                        i += 3;
+                    }
+                    b'u' => {
+                        decode_hex4(bytes, i + 1)?;
+                        i += 5;
+                    }
+                    b'\r' if bytes.get(i + 1) == Some(&b'\n') => i += 2,
+                    _ => i += 1,
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    state.offset = i + 1;
+    Some((Span::new(start + 1, i, state.src), has_escapes))
+}
+
+/// JSON5 string scanner returning the content span (used by SpanParser).
+#[inline(always)]
+pub(crate) fn json5_string_fast<'a>(state: &mut ParserState<'a>) -> Option<Span<'a>> {
+    json5_string_inner(state).map(|(span, _)| span)
+}
+
+/// Scan a JSON5 string, borrowing when it has no escapes.
+#[inline(always)]
+pub(crate) fn json5_string_cow<'a>(state: &mut ParserState<'a>) -> Option<Cow<'a, str>> {
+    let (span, has_escapes) = json5_string_inner(state)?;
+    if !has_escapes {
+        return Some(Cow::Borrowed(span.as_str()));
+    }
+    unescape_json5_string(span.as_str()).map(Cow::Owned)
+}
+
+/// Decode the escape sequences of a JSON5 string body (without quotes).
+/// Beyond JSON's escapes this handles `\'`, `\v`, `\0`, `\xHH`, line
+/// continuations, and identity escapes (`\a` → `a`).
+/// Returns None on malformed escapes or unpaired surrogates.
+#[cold]
+#[inline(never)]
+pub fn unescape_json5_string(raw: &str) -> Option<String> {
+    let bytes = raw.as_bytes();
+    let mut out = String::with_capacity(raw.len());
+    let mut i = 0;
+    while let Some(pos) = memchr::memchr(b'\\', &bytes[i..]) {
+        out.push_str(&raw[i..i + pos]);
+        i += pos + 1;
+        let c = raw[i..].chars().next()?;
+        i += c.len_utf8();
+        match c {
+            'b' => out.push('\u{08}'),
+            'f' => out.push('\u{0C}'),
+            'n' => out.push('\n'),
+            'r' => out.push('\r'),
+            '\r' => {
+                // `\` CR LF is a single line continuation
+                if bytes.get(i) == Some(&b'\n') {
+                    i += 1;
+                }
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => {}
+            't' => out.push('\t'),
+            'v' => out.push('\u{0B}'),
+            '0' if !bytes.get(i).is_some_and(u8::is_ascii_digit) => out.push('\0'),
+            '1'..='9' | '0' => return None,
+            'x' => {
+                let hi = decode_hex_nibble(*bytes.get(i)?)?;
+                let lo = decode_hex_nibble(*bytes.get(i + 1)?)?;
+                out.push(char::from((hi << 4 | lo) as u8));
+                i += 2;
+            }
+            'u' => {
+                let hi = decode_hex4(bytes, i)? as u32;
+                i += 4;
+                let cp = if (0xD800..=0xDBFF).contains(&hi) {
+                    if bytes.get(i..i + 2) != Some(b"\\u") {
+                        return None;
+                    }
+                    let lo = decode_hex4(bytes, i + 2)? as u32;
+                    if !(0xDC00..=0xDFFF).contains(&lo) {
+                        return None;
+                    }
+                    i += 6;
+                    0x10000 + ((hi - 0xD800) << 10) + (lo - 0xDC00)
+                } else {
+                    hi
+                };
+                out.push(char::from_u32(cp)?);
+            }
+            other => out.push(other),
+        }
+    }
+    out.push_str(&raw[i..]);
+    Some(out)
+}
+
+// ── Number scanner ────────────────────────────────────────────
+
+/// Scans `[+-]? (Infinity | NaN | 0[xX]hex+ | decimal)` where decimal is
+/// `(digits [. digits*] | . digits) ([eE] [+-]? digits)?`.
+/// Leading zeros (`007`) are rejected as in JSON.
+pub(crate) fn json5_number_fast<'a>(state: &mut ParserState<'a>) -> Option<Span<'a>> {
+    let bytes = state.src_bytes;
+    let start = state.offset;
+    let is_digit = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_digit);
+    let mut i = start;
+
+    if matches!(bytes.get(i), Some(b'+' | b'-')) {
+        i += 1;
+    }
+
+    let rest = bytes.get(i..)?;
+    if rest.starts_with(b"Infinity") {
+        i += 8;
+    } else if rest.starts_with(b"NaN") {
+        i += 3;
+    } else if rest.len() >= 2 && rest[0] == b'0' && matches!(rest[1], b'x' | b'X') {
+        i += 2;
+        let hex_start = i;
+        while bytes.get(i).is_some_and(u8::is_ascii_hexdigit) {
+            i += 1;
+        }
+        if i == hex_start {
+            return None;
+        }
+    } else {
+        let int_start = i;
+        while is_digit(i) {
+            i += 1;
+        }
+        let int_digits = i - int_start;
+        if int_digits > 1 && bytes[int_start] == b'0' {
+            return None;
+        }
+        let mut frac_digits = 0;
+        if bytes.get(i) == Some(&b'.') {
+            i += 1;
+            let frac_start = i;
+            while is_digit(i) {
+                i += 1;
+            }
+            frac_digits = i - frac_start;
+        }
+        if int_digits == 0 && frac_digits == 0 {
+            return None;
+        }
+        if matches!(bytes.get(i), Some(b'e' | b'E')) {
+            i += 1;
+            if matches!(bytes.get(i), Some(b'+' | b'-')) {
+                i += 1;
+            }
+            if !is_digit(i) {
+                return None;
+            }
+            while is_digit(i) {
+                i += 1;
+            }
+        }
+    }
+
+    state.offset = i;
+    Some(Span::new(start, i, state.src))
+}
+
+/// Convert a span produced by `json5_number_fast` to its f64 value.
+pub(crate) fn json5_number_value(span: Span<'_>) -> f64 {
+    let s = span.as_str();
+    let (neg, body) = match s.as_bytes()[0] {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let magnitude = match body.as_bytes() {
+        b"Infinity" => f64::INFINITY,
+        b"NaN" => f64::NAN,
+        [b'0', b'x' | b'X', hex @ ..] => hex.iter().fold(0.0, |acc, &b| {
+            acc * 16.0 + decode_hex_nibble(b).unwrap_or(0) as f64
+        }),
+        _ => body
+            .parse()
+            .expect("sp_json5_number must only yield valid JSON5 numbers"),
+    };
+    if neg { -magnitude } else { magnitude }
+}
+
+// ── Identifier scanner ────────────────────────────────────────
+
+/// Scan an ECMAScript IdentifierName for unquoted keys: `[A-Za-z$_]` or a
+/// Unicode letter, then also digits and Unicode alphanumerics.
+/// `\uXXXX` escapes inside identifiers are not supported.
+pub(crate) fn json5_identifier_fast<'a>(state: &mut ParserState<'a>) -> Option<Span<'a>> {
+    let start = state.offset;
+    let mut end = start;
+    for (idx, c) in state.src.get(start..)?.char_indices() {
+        let ok = match c {
+            'a'..='z' | 'A'..='Z' | '$' | '_' => true,
+            '0'..='9' => idx > 0,
+            '\u{200C}' | '\u{200D}' => idx > 0,
+            c if !c.is_ascii() => {
+                if idx == 0 {
+                    c.is_alphabetic()
+                } else {
+                    c.is_alphanumeric()
+                }
+            }
+            _ => false,
+        };
+        if !ok {
+            break;
+        }
+        end = start + idx + c.len_utf8();
+    }
+    if end == start {
+        return None;
+    }
+    state.offset = end;
+    Some(Span::new(start, end, state.src))
+}
+
+// ── Value parser ──────────────────────────────────────────────
+
+/// A parsed JSON5 document along with every comment in it, in source order.
+/// Comment spans include their `//` or `/* */` delimiters.
+#[derive(Pretty, Debug, Clone, PartialEq)]
+pub struct Json5Document<'a> {
+    pub value: JsonValue<'a>,
+    pub comments: Vec<Span<'a>>,
+}
+
+/// JSON5 value parser (no surrounding trivia). Comments are skipped.
+pub fn json5_value<'a>() -> Parser<'a, JsonValue<'a>> {
+    crate::recursive(json5_value_with)
+}
+
+/// The JSON5 grammar, with nested values parsed by `value`.
+fn json5_value_with<'a>(value: crate::Recursive<'a, JsonValue<'a>>) -> Parser<'a, JsonValue<'a>> {
+    // ── Leaf values ───────────────────────────────────────────
+
+    let json5_null: Parser<'a, JsonValue<'a>> = sp_string("null").map(|_| JsonValue::Null);
+    let json5_true: Parser<'a, JsonValue<'a>> = sp_string("true").map(|_| JsonValue::Bool(true));
+    let json5_false: Parser<'a, JsonValue<'a>> = sp_string("false").map(|_| JsonValue::Bool(false));
+
+    let json5_number: Parser<'a, JsonValue<'a>> =
+        sp_json5_number().map(|span| JsonValue::Number(json5_number_value(span)));
+
+    let json5_string = Parser::new(move |state: &mut ParserState<'a>| {
+        let result = json5_string_cow(state);
+        #[cfg(feature = "diagnostics")]
+        if result.is_none() {
+            state.add_expected("string");
+        }
+        result.map(JsonValue::String)
+    });
+
+    // ── Array: trailing comma allowed ─────────────────────────
+
+    let json5_array = {
+        let value = value.parser();
+        let open = sp_string("[");
+        let close = sp_string("]");
+        let comma = sp_string(",");
+
+        Parser::new(move |state: &mut ParserState<'a>| {
+            open.call(state)?;
+
+            let mut items = Vec::with_capacity(4);
+            loop {
+                skip_trivia(state)?;
+                if close.call(state).is_some() {
+                    break;
+                }
+                items.push(value.call(state)?);
+                skip_trivia(state)?;
+                if comma.call(state).is_none() {
+                    close.call(state)?;
+                    break;
+                }
+            }
+            Some(JsonValue::Array(Box::new(items)))
+        })
+    };
+
+    // ── Object: identifier or string keys, trailing comma allowed ──
+
+    let json5_object = {
+        let value = value.parser();
+        let ident = sp_json5_identifier();
+        let open = sp_string("{");
+        let close = sp_string("}");
+        let colon = sp_string(":");
+        let comma = sp_string(",");
+
+        Parser::new(move |state: &mut ParserState<'a>| {
+            open.call(state)?;
+
+            let mut entries = Vec::with_capacity(4);
+            loop {
+                skip_trivia(state)?;
+                if close.call(state).is_some() {
+                    break;
+                }
+                let k = match state.src_bytes.get(state.offset) {
+                    Some(b'"' | b'\'') => json5_string_cow(state),
+                    _ => ident.call(state).map(|span| Cow::Borrowed(span.as_str())),
+                };
+                let Some(k) = k else {
+                    #[cfg(feature = "diagnostics")]
+                    state.add_expected("string or identifier key");
+                    return None;
+                };
+                skip_trivia(state)?;
+                colon.call(state)?;
+                skip_trivia(state)?;
+                let v = value.call(state)?;
+                entries.push((k, v));
+                skip_trivia(state)?;
+                if comma.call(state).is_none() {
+                    close.call(state)?;
+                    break;
+                }
+            }
+            Some(JsonValue::Object(Box::new(entries)))
+        })
+    };
+
+    // ── First-byte dispatch ───────────────────────────────────
+
+    crate::leaf::dispatch_byte_multi(vec![
+        (b"{" as &[u8], json5_object),
+        (b"[", json5_array),
+        (b"\"'", json5_string),
+        (b"t", json5_true),
+        (b"f", json5_false),
+        (b"n", json5_null),
+        (b"+-.0123456789IN", json5_number),
+    ])
+}
+
+/// JSON5 / JSONC parser: a value surrounded by optional whitespace and comments.
+pub fn json5_parser<'a>() -> Parser<'a, JsonValue<'a>> {
+    let value = json5_value();
+    Parser::new(move |state: &mut ParserState<'a>| trivia_around(&value, state))
+}
+
+fn trivia_around<'a>(
+    value: &Parser<'a, JsonValue<'a>>,
+    state: &mut ParserState<'a>,
+) -> Option<JsonValue<'a>> {
+    skip_trivia(state)?;
+    let v = value.call(state)?;
+    skip_trivia(state)?;
+    Some(v)
+}
+
+/// Like `json5_parser()`, but also returns the span of every comment for
+/// tooling (formatters, doc extraction, round-tripping).
+pub fn json5_document_parser<'a>() -> Parser<'a, Json5Document<'a>> {
+    let value = json5_value();
+    Parser::new(move |state: &mut ParserState<'a>| {
+        let start = state.offset;
+        let value = trivia_around(&value, state)?;
+        Some(Json5Document {
+            value,
+            comments: comments_between(state, start),
+        })
+    })
+}
+
+/// Comments in the document just parsed from `start` to the current offset.
+/// Outside string literals, a `/` there can only open a comment.
+fn comments_between<'a>(state: &mut ParserState<'a>, start: usize) -> Vec<Span<'a>> {
+    let end = state.offset;
+    let mut comments = Vec::new();
+    state.offset = start;
+    while let Some(p) = memchr::memchr3(b'/', b'"', b'\'', &state.src_bytes[state.offset..end]) {
+        state.offset += p;
+        let scanned = if state.src_bytes[state.offset] == b'/' {
+            json5_comment_fast(state).map(|comment| comments.push(comment))
+        } else {
+            json5_string_inner(state).map(|_| ())
+        };
+        if scanned.is_none() {
+            break;
+        }
+    }
+    state.offset = end;
+    comments
+}
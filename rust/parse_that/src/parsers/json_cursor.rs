@@ -0,0 +1,308 @@
+// On-demand JSON navigation: walk into a document without building `JsonValue`
+// nodes. Values that are stepped over are skipped with the monolithic scanners
+// (strings, numbers) and a SIMD bracket matcher (containers); only the value
+// you land on is ever materialized.
+//
+// Skipped containers are matched structurally, not validated. Call `value()`
+// on a cursor to fully parse (and validate) just that subtree.
+
+use std::borrow::Cow;
+
+use crate::leaf::trim_leading_whitespace_mut;
+use crate::parsers::json::{
+    JsonValue, json_string_cow, json_string_fast, json_value, number_span_fast_ex,
+    parse_json_number_f64,
+};
+use crate::state::{ParserState, Span};
+
+/// The type of the value under a cursor, decided from its first byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonKind {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+// ── Skipping ──────────────────────────────────────────────────
+
+/// Find the next `"`, `[`, `]`, `{` or `}` at or after `i`.
+/// `[`/`]` differ from `{`/`}` only in bit 0x20, so two compares cover four brackets.
+#[inline(always)]
+fn find_structural(bytes: &[u8], mut i: usize) -> Option<usize> {
+    let end = bytes.len();
+    {
+        use std::simd::prelude::*;
+        while i + 16 <= end {
+            let chunk = u8x16::from_slice(&bytes[i..i + 16]);
+            let folded = chunk | u8x16::splat(0x20);
+            let mask = chunk.simd_eq(u8x16::splat(b'"'))
+                | folded.simd_eq(u8x16::splat(b'{'))
+                | folded.simd_eq(u8x16::splat(b'}'));
+            if mask.any() {
+                return Some(i + mask.to_bitmask().trailing_zeros() as usize);
+            }
+            i += 16;
+        }
+    }
+    while i < end {
+        match unsafe { *bytes.get_unchecked(i) } {
+            b'"' | b'[' | b']' | b'{' | b'}' => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Skip an array or object starting at `state.offset` by bracket matching.
+#[inline]
+fn skip_container(state: &mut ParserState<'_>) -> Option<()> {
+    let bytes = state.src_bytes;
+    let mut depth = 0usize;
+    loop {
+        state.offset = find_structural(bytes, state.offset)?;
+        match unsafe { *bytes.get_unchecked(state.offset) } {
+            b'"' => {
+                json_string_fast(state)?;
+            }
+            b'[' | b'{' => {
+                depth += 1;
+                state.offset += 1;
+            }
+            _ => {
+                depth -= 1;
+                state.offset += 1;
+                if depth == 0 {
+                    return Some(());
+                }
+            }
+        }
+    }
+}
+
+/// Skip the value starting at `state.offset`.
+#[inline]
+fn skip_value(state: &mut ParserState<'_>) -> Option<()> {
+    let rest = state.src_bytes.get(state.offset..)?;
+    match *rest.first()? {
+        b'"' => json_string_fast(state).map(|_| ()),
+        b'[' | b'{' => skip_container(state),
+        b'-' | b'0'..=b'9' => number_span_fast_ex(state).map(|_| ()),
+        b't' if rest.starts_with(b"true") => {
+            state.offset += 4;
+            Some(())
+        }
+        b'f' if rest.starts_with(b"false") => {
+            state.offset += 5;
+            Some(())
+        }
+        b'n' if rest.starts_with(b"null") => {
+            state.offset += 4;
+            Some(())
+        }
+        _ => None,
+    }
+}
+
+/// After a value: skip it and its trailing `,`. None at the closing bracket
+/// (or on malformed input).
+#[inline]
+fn next_item(state: &mut ParserState<'_>) -> Option<()> {
+    skip_value(state)?;
+    trim_leading_whitespace_mut(state);
+    if state.src_bytes.get(state.offset) != Some(&b',') {
+        return None;
+    }
+    state.offset += 1;
+    trim_leading_whitespace_mut(state);
+    Some(())
+}
+
+/// Enter a container opening with `open`; None if it isn't one or is empty.
+#[inline]
+fn enter(src: &str, offset: usize, open: u8, close: u8) -> Option<ParserState<'_>> {
+    let mut state = ParserState::new(src);
+    state.offset = offset;
+    if state.src_bytes.get(offset) != Some(&open) {
+        return None;
+    }
+    state.offset += 1;
+    trim_leading_whitespace_mut(&mut state);
+    (state.src_bytes.get(state.offset) != Some(&close)).then_some(state)
+}
+
+// ── Cursor ────────────────────────────────────────────────────
+
+/// A position at the start of a JSON value. Cheap to copy; navigation never
+/// allocates except to decode escaped object keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonCursor<'a> {
+    src: &'a str,
+    offset: usize,
+}
+
+impl<'a> JsonCursor<'a> {
+    /// A cursor at the top-level value of `src` (leading whitespace skipped).
+    pub fn new(src: &'a str) -> Self {
+        let mut state = ParserState::new(src);
+        trim_leading_whitespace_mut(&mut state);
+        JsonCursor {
+            src,
+            offset: state.offset,
+        }
+    }
+
+    /// Byte offset of the value in the source.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn kind(&self) -> Option<JsonKind> {
+        Some(match self.src.as_bytes().get(self.offset)? {
+            b'n' => JsonKind::Null,
+            b't' | b'f' => JsonKind::Bool,
+            b'-' | b'0'..=b'9' => JsonKind::Number,
+            b'"' => JsonKind::String,
+            b'[' => JsonKind::Array,
+            b'{' => JsonKind::Object,
+            _ => return None,
+        })
+    }
+
+    /// Source span of the whole value, found by skipping it.
+    pub fn span(&self) -> Option<Span<'a>> {
+        let mut state = ParserState::new(self.src);
+        state.offset = self.offset;
+        skip_value(&mut state)?;
+        Some(Span::new(self.offset, state.offset, self.src))
+    }
+
+    /// Step into the member `key` of an object (first match).
+    pub fn get(&self, key: &str) -> Option<JsonCursor<'a>> {
+        self.entries().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Step into element `n` of an array.
+    pub fn index(&self, n: usize) -> Option<JsonCursor<'a>> {
+        self.elements().nth(n)
+    }
+
+    /// Iterate array elements; empty if this is not an array.
+    pub fn elements(&self) -> JsonElements<'a> {
+        JsonElements {
+            state: enter(self.src, self.offset, b'[', b']'),
+            pending: false,
+        }
+    }
+
+    /// Iterate object members in source order; empty if this is not an object.
+    pub fn entries(&self) -> JsonEntries<'a> {
+        JsonEntries {
+            state: enter(self.src, self.offset, b'{', b'}'),
+            pending: false,
+        }
+    }
+
+    /// Parse the value under the cursor into a `JsonValue`.
+    pub fn value(&self) -> Option<JsonValue<'a>> {
+        let mut state = ParserState::new(self.src);
+        state.offset = self.offset;
+        json_value().call(&mut state)
+    }
+
+    pub fn as_str(&self) -> Option<Cow<'a, str>> {
+        let mut state = ParserState::new(self.src);
+        state.offset = self.offset;
+        json_string_cow(&mut state)
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        let mut state = ParserState::new(self.src);
+        state.offset = self.offset;
+        let ns = number_span_fast_ex(&mut state)?;
+        Some(parse_json_number_f64(ns.span, ns.is_integer))
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        let rest = self.src.as_bytes().get(self.offset..)?;
+        if rest.starts_with(b"true") {
+            Some(true)
+        } else if rest.starts_with(b"false") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.src.as_bytes()[self.offset..].starts_with(b"null")
+    }
+}
+
+/// Iterator over the elements of an array cursor.
+pub struct JsonElements<'a> {
+    /// Positioned at the next element; None once exhausted.
+    state: Option<ParserState<'a>>,
+    /// Whether the element at `state.offset` was already yielded.
+    pending: bool,
+}
+
+impl<'a> Iterator for JsonElements<'a> {
+    type Item = JsonCursor<'a>;
+
+    fn next(&mut self) -> Option<JsonCursor<'a>> {
+        let state = self.state.as_mut()?;
+        if self.pending && next_item(state).is_none() {
+            self.state = None;
+            return None;
+        }
+        self.pending = true;
+        Some(JsonCursor {
+            src: state.src,
+            offset: state.offset,
+        })
+    }
+}
+
+/// Iterator over the `(key, value)` members of an object cursor.
+pub struct JsonEntries<'a> {
+    state: Option<ParserState<'a>>,
+    pending: bool,
+}
+
+impl<'a> JsonEntries<'a> {
+    fn member(state: &mut ParserState<'a>) -> Option<(Cow<'a, str>, JsonCursor<'a>)> {
+        let key = json_string_cow(state)?;
+        trim_leading_whitespace_mut(state);
+        if state.src_bytes.get(state.offset) != Some(&b':') {
+            return None;
+        }
+        state.offset += 1;
+        trim_leading_whitespace_mut(state);
+        let value = JsonCursor {
+            src: state.src,
+            offset: state.offset,
+        };
+        Some((key, value))
+    }
+}
+
+impl<'a> Iterator for JsonEntries<'a> {
+    type Item = (Cow<'a, str>, JsonCursor<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = self.state.as_mut()?;
+        if self.pending && next_item(state).is_none() {
+            self.state = None;
+            return None;
+        }
+        self.pending = true;
+        let member = Self::member(state);
+        if member.is_none() {
+            self.state = None;
+        }
+        member
+    }
+}
@@ -0,0 +1,202 @@
+// Lossless JSON: numbers keep enough information to reproduce their source
+// text. `json_value()` folds every number into an `f64`, which silently
+// rounds integers above 2^53; `json_value_lossless()` instead keeps integers
+// as `i64`/`u64`, and falls back to the raw source span whenever neither an
+// integer nor the shortest `f64` rendering would write the same digits back.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use crate::parse::*;
+use crate::parsers::json::{
+    JsonTree, JsonValue, NumberSpan, json_value_with, parse_json_number_f64,
+};
+use crate::parsers::json_write::{
+    JsonFormat, write_array, write_json_number, write_json_string, write_object, write_to_string,
+};
+use crate::state::Span;
+
+#[derive(Debug, Clone, Copy)]
+pub enum JsonNumber<'a> {
+    I64(i64),
+    /// Integers above `i64::MAX` that still fit in a `u64`.
+    U64(u64),
+    /// Written back exactly by the shortest round-trip formatting.
+    F64(f64),
+    /// Anything else (`1.50`, `1E3`, 2^64, ...), verbatim.
+    Raw(Span<'a>),
+}
+
+impl<'a> JsonNumber<'a> {
+    fn from_span(ns: NumberSpan<'a>) -> Self {
+        let text = ns.span.as_str();
+        // `-0` goes down the float path so it keeps its sign
+        if ns.is_integer && text != "-0" {
+            if let Ok(n) = text.parse() {
+                return JsonNumber::I64(n);
+            }
+            if let Ok(n) = text.parse() {
+                return JsonNumber::U64(n);
+            }
+            return JsonNumber::Raw(ns.span);
+        }
+        let n = parse_json_number_f64(ns.span, ns.is_integer);
+        let mut shortest = Vec::with_capacity(24);
+        let _ = write_json_number(&mut shortest, n);
+        if shortest == text.as_bytes() {
+            JsonNumber::F64(n)
+        } else {
+            JsonNumber::Raw(ns.span)
+        }
+    }
+
+    /// Nearest `f64` (what `json_value()` would have produced).
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            JsonNumber::I64(n) => n as f64,
+            JsonNumber::U64(n) => n as f64,
+            JsonNumber::F64(n) => n,
+            JsonNumber::Raw(span) => parse_json_number_f64(span, false),
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            JsonNumber::I64(n) => Some(n),
+            JsonNumber::U64(n) => n.try_into().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            JsonNumber::I64(n) => n.try_into().ok(),
+            JsonNumber::U64(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn write_json<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        match *self {
+            JsonNumber::I64(n) => write!(w, "{n}"),
+            JsonNumber::U64(n) => write!(w, "{n}"),
+            JsonNumber::F64(n) => write_json_number(w, n),
+            JsonNumber::Raw(span) => w.write_all(span.as_str().as_bytes()),
+        }
+    }
+}
+
+/// `Raw` numbers compare by text, not by source position.
+impl PartialEq for JsonNumber<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (JsonNumber::I64(a), JsonNumber::I64(b)) => a == b,
+            (JsonNumber::U64(a), JsonNumber::U64(b)) => a == b,
+            (JsonNumber::F64(a), JsonNumber::F64(b)) => a == b,
+            (JsonNumber::Raw(a), JsonNumber::Raw(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+/// `JsonValue` with lossless numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValueLossless<'a> {
+    Null,
+    Bool(bool),
+    Number(JsonNumber<'a>),
+    String(Cow<'a, str>),
+    Array(Box<Vec<JsonValueLossless<'a>>>),
+    Object(Box<Vec<(Cow<'a, str>, JsonValueLossless<'a>)>>),
+}
+
+fn write_value<W: Write>(
+    w: &mut W,
+    value: &JsonValueLossless<'_>,
+    format: JsonFormat,
+    depth: usize,
+) -> io::Result<()> {
+    let item = |w: &mut W, v: &JsonValueLossless<'_>, depth| write_value(w, v, format, depth);
+    match value {
+        JsonValueLossless::Null => w.write_all(b"null"),
+        JsonValueLossless::Bool(true) => w.write_all(b"true"),
+        JsonValueLossless::Bool(false) => w.write_all(b"false"),
+        JsonValueLossless::Number(n) => n.write_json(w),
+        JsonValueLossless::String(s) => write_json_string(w, s),
+        JsonValueLossless::Array(items) => write_array(w, items, format, depth, item),
+        JsonValueLossless::Object(entries) => write_object(w, entries, format, depth, item),
+    }
+}
+
+impl JsonValueLossless<'_> {
+    /// Serialize as compact JSON; every number is written with its source digits.
+    pub fn to_json_string(&self) -> String {
+        self.to_json_string_with(JsonFormat::Compact)
+    }
+
+    pub fn to_json_string_with(&self, format: JsonFormat) -> String {
+        write_to_string(|buf| write_value(buf, self, format, 0))
+    }
+
+    pub fn write_json<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_json_with(w, JsonFormat::Compact)
+    }
+
+    pub fn write_json_with<W: Write>(&self, w: &mut W, format: JsonFormat) -> io::Result<()> {
+        write_value(w, self, format, 0)
+    }
+}
+
+impl<'a> JsonTree<'a> for JsonValueLossless<'a> {
+    fn null() -> Self {
+        JsonValueLossless::Null
+    }
+
+    fn bool(b: bool) -> Self {
+        JsonValueLossless::Bool(b)
+    }
+
+    #[inline]
+    fn number(ns: NumberSpan<'a>) -> Self {
+        JsonValueLossless::Number(JsonNumber::from_span(ns))
+    }
+
+    fn string(s: Cow<'a, str>) -> Self {
+        JsonValueLossless::String(s)
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        JsonValueLossless::Array(Box::new(items))
+    }
+
+    fn object(entries: Vec<(Cow<'a, str>, Self)>) -> Self {
+        JsonValueLossless::Object(Box::new(entries))
+    }
+}
+
+impl<'a> From<JsonValueLossless<'a>> for JsonValue<'a> {
+    /// Round numbers to `f64`.
+    fn from(value: JsonValueLossless<'a>) -> Self {
+        match value {
+            JsonValueLossless::Null => JsonValue::Null,
+            JsonValueLossless::Bool(b) => JsonValue::Bool(b),
+            JsonValueLossless::Number(n) => JsonValue::Number(n.as_f64()),
+            JsonValueLossless::String(s) => JsonValue::String(s),
+            JsonValueLossless::Array(items) => {
+                JsonValue::Array(Box::new(items.into_iter().map(Into::into).collect()))
+            }
+            JsonValueLossless::Object(entries) => JsonValue::Object(Box::new(
+                entries.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            )),
+        }
+    }
+}
+
+/// `json_value()` with lossless numbers; same grammar and leniency.
+pub fn json_value_lossless<'a>() -> Parser<'a, JsonValueLossless<'a>> {
+    crate::recursive(json_value_with)
+}
+
+pub fn json_parser_lossless<'a>() -> Parser<'a, JsonValueLossless<'a>> {
+    json_value_lossless().trim_whitespace()
+}
@@ -0,0 +1,354 @@
+// JSONPath subset over `JsonValue`, with the expression grammar built from
+// the crate's own combinators.
+//
+// Supported: `$` root, `.name`, `.*`, `..name` / `..*` / `..[...]`
+// (descendants), `['name']` / `["name"]`, `[n]` (negative from end),
+// `[start:end]`, `[*]`, unions `[a, b]`, and filters
+// `[?(@.price < 10 && @.category == 'fiction')]` with `== != < <= > >=`,
+// `&&`, `||`, `!`, parentheses, and bare `@.path` existence tests.
+
+use std::borrow::Cow;
+
+use crate::combinators::{Recursive, recursive};
+use crate::leaf::*;
+use crate::parse::*;
+use crate::parsers::json::{JsonValue, json_value};
+use crate::parsers::json5::json5_string_cow;
+use crate::state::ParserState;
+
+// ── AST ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector<'a> {
+    Name(Cow<'a, str>),
+    Wildcard,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>),
+    Union(Vec<Selector<'a>>),
+    Filter(Box<FilterExpr<'a>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment<'a> {
+    /// `.sel` or `[sel]` — applies to the current nodes' children.
+    Child(Selector<'a>),
+    /// `..sel` — applies to the current nodes and all their descendants.
+    Descendant(Selector<'a>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand<'a> {
+    /// `@...` — a query relative to the current node.
+    Current(Vec<Segment<'a>>),
+    Literal(JsonValue<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr<'a> {
+    /// Bare `@.path`: true when the relative query selects anything.
+    Exists(Vec<Segment<'a>>),
+    Compare(Operand<'a>, CmpOp, Operand<'a>),
+    Not(Box<FilterExpr<'a>>),
+    And(Vec<FilterExpr<'a>>),
+    Or(Vec<FilterExpr<'a>>),
+}
+
+/// A compiled JSONPath expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath<'a> {
+    pub segments: Vec<Segment<'a>>,
+}
+
+// ── Grammar ───────────────────────────────────────────────────
+
+fn member_name<'a>() -> Parser<'a, Cow<'a, str>> {
+    regex(r"[A-Za-z_$\u{80}-\u{10FFFF}][A-Za-z0-9_$\-\u{80}-\u{10FFFF}]*").map(Cow::Borrowed)
+}
+
+fn quoted<'a>() -> Parser<'a, Cow<'a, str>> {
+    Parser::new(|state: &mut ParserState<'a>| json5_string_cow(state))
+}
+
+fn integer<'a>() -> Parser<'a, i64> {
+    regex(r"-?[0-9]+").map(|s| {
+        s.parse().unwrap_or(if s.starts_with('-') {
+            i64::MIN
+        } else {
+            i64::MAX
+        })
+    })
+}
+
+fn token<'a>(s: &'static str) -> Parser<'a, &'a str> {
+    string(s).trim_whitespace()
+}
+
+fn cmp_op<'a>() -> Parser<'a, CmpOp> {
+    (string("==").map(|_| CmpOp::Eq)
+        | string("!=").map(|_| CmpOp::Ne)
+        | string("<=").map(|_| CmpOp::Le)
+        | string(">=").map(|_| CmpOp::Ge)
+        | string("<").map(|_| CmpOp::Lt)
+        | string(">").map(|_| CmpOp::Gt))
+    .trim_whitespace()
+}
+
+fn operand<'a>(segments: &Recursive<'a, Vec<Segment<'a>>>) -> Parser<'a, Operand<'a>> {
+    let current = string("@").next(segments.parser()).map(Operand::Current);
+    let literal = (quoted().map(JsonValue::String) | json_value()).map(Operand::Literal);
+    (current | literal).trim_whitespace()
+}
+
+fn filter_expr<'a>(segments: &Recursive<'a, Vec<Segment<'a>>>) -> Parser<'a, FilterExpr<'a>> {
+    recursive(|filter| {
+        let lhs = operand(segments);
+        let rhs = cmp_op().then(operand(segments)).save_state().opt();
+        let comparison = Parser::new(move |state: &mut ParserState<'a>| {
+            match (lhs.call(state)?, rhs.call(state)?) {
+                (lhs, Some((op, rhs))) => Some(FilterExpr::Compare(lhs, op, rhs)),
+                (Operand::Current(path), None) => Some(FilterExpr::Exists(path)),
+                // A bare literal is not a test
+                (Operand::Literal(_), None) => None,
+            }
+        });
+
+        let group = filter.parser().wrap(token("("), token(")"));
+        let negated = token("!")
+            .next(filter.parser().wrap(token("("), token(")")))
+            .map(|e| FilterExpr::Not(Box::new(e)));
+        let primary = negated | group | comparison;
+
+        let and = primary.sep_by(token("&&"), 1..).map(|mut terms| {
+            if terms.len() == 1 {
+                terms.pop().unwrap()
+            } else {
+                FilterExpr::And(terms)
+            }
+        });
+        and.sep_by(token("||"), 1..).map(|mut terms| {
+            if terms.len() == 1 {
+                terms.pop().unwrap()
+            } else {
+                FilterExpr::Or(terms)
+            }
+        })
+    })
+}
+
+fn bracket_selector<'a>(segments: &Recursive<'a, Vec<Segment<'a>>>) -> Parser<'a, Selector<'a>> {
+    let wildcard = string("*").map(|_| Selector::Wildcard);
+    let name = quoted().map(Selector::Name);
+    let filter = string("?")
+        .next(filter_expr(segments))
+        .map(|e| Selector::Filter(Box::new(e)));
+    let slice = integer()
+        .opt()
+        .skip(token(":"))
+        .then(integer().opt())
+        .map(|(start, end)| Selector::Slice(start, end));
+    let index = integer().map(Selector::Index);
+
+    (wildcard | name | filter | slice | index).trim_whitespace()
+}
+
+fn bracket<'a>(segments: &Recursive<'a, Vec<Segment<'a>>>) -> Parser<'a, Selector<'a>> {
+    bracket_selector(segments)
+        .sep_by(string(","), 1..)
+        .wrap(string("["), string("]"))
+        .map(|mut sels| {
+            if sels.len() == 1 {
+                sels.pop().unwrap()
+            } else {
+                Selector::Union(sels)
+            }
+        })
+}
+
+/// The segment list, built once: filters refer back to it (`@.path`) through
+/// the `recursive` handle rather than building a copy per nesting level.
+fn segments<'a>() -> Parser<'a, Vec<Segment<'a>>> {
+    recursive(|segments| {
+        let dot_selector =
+            || string("*").map(|_| Selector::Wildcard) | member_name().map(Selector::Name);
+        let bracket = bracket(&segments).shared();
+
+        let descendant = string("..")
+            .next(dot_selector() | bracket.parser())
+            .map(Segment::Descendant);
+        let child = string(".").next(dot_selector()).map(Segment::Child);
+        let bracketed = bracket.parser().map(Segment::Child);
+
+        (descendant | child | bracketed).many(..)
+    })
+}
+
+/// Parser for a full JSONPath expression: `$` followed by segments.
+pub fn json_path_parser<'a>() -> Parser<'a, JsonPath<'a>> {
+    string("$")
+        .next(segments())
+        .map(|segments| JsonPath { segments })
+        .trim_whitespace()
+        .eof()
+}
+
+// ── Evaluation ────────────────────────────────────────────────
+
+/// Resolve a possibly-negative index against `len`.
+fn normalize_index(i: i64, len: usize) -> i64 {
+    if i < 0 { len as i64 + i } else { i }
+}
+
+fn children<'v, 'j>(node: &'v JsonValue<'j>) -> Box<dyn Iterator<Item = &'v JsonValue<'j>> + 'v> {
+    match node {
+        JsonValue::Array(items) => Box::new(items.iter()),
+        JsonValue::Object(entries) => Box::new(entries.iter().map(|(_, v)| v)),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn select<'v, 'j>(sel: &Selector<'_>, node: &'v JsonValue<'j>, out: &mut Vec<&'v JsonValue<'j>>) {
+    match sel {
+        Selector::Name(name) => {
+            if let JsonValue::Object(entries) = node
+                && let Some((_, v)) = entries.iter().find(|(k, _)| k == name)
+            {
+                out.push(v);
+            }
+        }
+        Selector::Wildcard => out.extend(children(node)),
+        Selector::Index(i) => {
+            if let JsonValue::Array(items) = node {
+                let i = normalize_index(*i, items.len());
+                if let Ok(i) = usize::try_from(i)
+                    && let Some(v) = items.get(i)
+                {
+                    out.push(v);
+                }
+            }
+        }
+        Selector::Slice(start, end) => {
+            if let JsonValue::Array(items) = node {
+                let len = items.len();
+                let clamp = |i: i64| normalize_index(i, len).clamp(0, len as i64) as usize;
+                let start = start.map_or(0, clamp);
+                let end = end.map_or(len, clamp);
+                if start < end {
+                    out.extend(&items[start..end]);
+                }
+            }
+        }
+        Selector::Union(sels) => {
+            for sel in sels {
+                select(sel, node, out);
+            }
+        }
+        Selector::Filter(expr) => out.extend(children(node).filter(|child| expr.test(child))),
+    }
+}
+
+fn descend<'v, 'j>(sel: &Selector<'_>, node: &'v JsonValue<'j>, out: &mut Vec<&'v JsonValue<'j>>) {
+    select(sel, node, out);
+    for child in children(node) {
+        descend(sel, child, out);
+    }
+}
+
+fn eval_segments<'v, 'j>(
+    segments: &[Segment<'_>],
+    root: &'v JsonValue<'j>,
+) -> Vec<&'v JsonValue<'j>> {
+    let mut nodes = vec![root];
+    for segment in segments {
+        let mut next = Vec::new();
+        for node in nodes {
+            match segment {
+                Segment::Child(sel) => select(sel, node, &mut next),
+                Segment::Descendant(sel) => descend(sel, node, &mut next),
+            }
+        }
+        nodes = next;
+    }
+    nodes
+}
+
+impl Operand<'_> {
+    /// Resolve to a single value; relative queries must select exactly one node.
+    fn resolve<'v, 'j>(&'v self, current: &'v JsonValue<'j>) -> Option<&'v JsonValue<'v>>
+    where
+        'j: 'v,
+    {
+        match self {
+            Operand::Literal(v) => Some(v),
+            Operand::Current(path) => match eval_segments(path, current).as_slice() {
+                [v] => Some(*v),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn compare(lhs: Option<&JsonValue<'_>>, op: CmpOp, rhs: Option<&JsonValue<'_>>) -> bool {
+    use std::cmp::Ordering;
+    let ordering = match (lhs, rhs) {
+        (Some(JsonValue::Number(a)), Some(JsonValue::Number(b))) => a.partial_cmp(b),
+        (Some(JsonValue::String(a)), Some(JsonValue::String(b))) => Some(a.cmp(b)),
+        // Missing operands only compare equal to each other
+        (None, None) => Some(Ordering::Equal),
+        (Some(a), Some(b)) if a == b => Some(Ordering::Equal),
+        _ => None,
+    };
+    match op {
+        CmpOp::Eq => ordering == Some(Ordering::Equal),
+        CmpOp::Ne => ordering != Some(Ordering::Equal),
+        CmpOp::Lt => ordering == Some(Ordering::Less),
+        CmpOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        CmpOp::Gt => ordering == Some(Ordering::Greater),
+        CmpOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+impl FilterExpr<'_> {
+    /// Evaluate the filter with `@` bound to `current`.
+    pub fn test(&self, current: &JsonValue<'_>) -> bool {
+        match self {
+            FilterExpr::Exists(path) => !eval_segments(path, current).is_empty(),
+            FilterExpr::Compare(lhs, op, rhs) => {
+                compare(lhs.resolve(current), *op, rhs.resolve(current))
+            }
+            FilterExpr::Not(e) => !e.test(current),
+            FilterExpr::And(terms) => terms.iter().all(|e| e.test(current)),
+            FilterExpr::Or(terms) => terms.iter().any(|e| e.test(current)),
+        }
+    }
+}
+
+impl<'a> JsonPath<'a> {
+    /// Compile a JSONPath expression.
+    pub fn parse(src: &'a str) -> Result<JsonPath<'a>, ParseError> {
+        json_path_parser().parse_or_error(src)
+    }
+
+    /// Select every matching node under `root`, in document order per segment.
+    pub fn query<'v, 'j>(&self, root: &'v JsonValue<'j>) -> Vec<&'v JsonValue<'j>> {
+        eval_segments(&self.segments, root)
+    }
+}
+
+impl<'j> JsonValue<'j> {
+    /// Compile `path` and run it against `self`. This builds the JSONPath
+    /// grammar and parses `path` on every call; to run one path repeatedly,
+    /// compile it once with `JsonPath::parse(path)?` and call its `query`.
+    pub fn query<'v>(&'v self, path: &str) -> Result<Vec<&'v JsonValue<'j>>, ParseError> {
+        Ok(JsonPath::parse(path)?.query(self))
+    }
+}
@@ -0,0 +1,404 @@
+// serde `Deserializer` driven directly by the JSON byte scanners — no
+// intermediate `JsonValue` tree. Gated behind the `serde` feature.
+
+use std::borrow::Cow;
+
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+use crate::leaf::trim_leading_whitespace_mut;
+use crate::parsers::json::{
+    JSON_STRICT_MAX_DEPTH, json_string_cow, number_span_fast_ex, parse_json_number_f64,
+};
+use crate::state::ParserState;
+
+// ── Error ─────────────────────────────────────────────────────
+
+/// Error returned by [`from_str`] and the [`Deserializer`].
+///
+/// Boxed so that every `Result` threaded through the visitor stays one word
+/// wide on the happy path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeError(Box<DeErrorInner>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DeErrorInner {
+    message: String,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl DeError {
+    #[cold]
+    fn at(state: &ParserState<'_>, message: impl Into<String>) -> Self {
+        DeError(Box::new(DeErrorInner {
+            message: message.into(),
+            offset: state.offset,
+            line: state.get_line_number(),
+            column: state.get_column_number(),
+        }))
+    }
+
+    pub fn message(&self) -> &str {
+        &self.0.message
+    }
+
+    /// Byte offset where deserialization stopped.
+    pub fn offset(&self) -> usize {
+        self.0.offset
+    }
+
+    /// 1-based line number of the failure.
+    pub fn line(&self) -> usize {
+        self.0.line
+    }
+
+    /// 0-based column number of the failure.
+    pub fn column(&self) -> usize {
+        self.0.column
+    }
+}
+
+impl std::fmt::Display for DeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {} (offset {})",
+            self.0.message, self.0.line, self.0.column, self.0.offset
+        )
+    }
+}
+
+impl std::error::Error for DeError {}
+
+impl de::Error for DeError {
+    /// Errors raised by `Deserialize` impls carry no location (line 0); the
+    /// deserializer fills it in on the way out (see [`from_str`]).
+    #[cold]
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        DeError(Box::new(DeErrorInner {
+            message: msg.to_string(),
+            offset: 0,
+            line: 0,
+            column: 0,
+        }))
+    }
+}
+
+// ── Deserializer ──────────────────────────────────────────────
+
+/// Deserializes JSON straight from a `ParserState`, using the same SIMD
+/// scanners as `json_parser()`. Unescaped strings are handed to visitors as
+/// borrowed `&'a str`, so `&'a str` / `Cow<'a, str>` fields are zero-copy.
+/// Arrays and objects nest at most [`JSON_STRICT_MAX_DEPTH`] deep.
+pub struct Deserializer<'a> {
+    state: ParserState<'a>,
+}
+
+impl<'a> Deserializer<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Deserializer {
+            state: ParserState {
+                max_depth: Some(JSON_STRICT_MAX_DEPTH),
+                ..ParserState::new(src)
+            },
+        }
+    }
+
+    /// Fails unless only whitespace remains.
+    pub fn end(&mut self) -> Result<(), DeError> {
+        trim_leading_whitespace_mut(&mut self.state);
+        if self.state.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error("trailing characters"))
+        }
+    }
+
+    #[inline]
+    fn error(&self, message: &str) -> DeError {
+        DeError::at(&self.state, message)
+    }
+
+    /// Attach the current location to errors raised by `Deserialize` impls.
+    #[cold]
+    fn locate(&self, mut err: DeError) -> DeError {
+        if err.0.line == 0 {
+            err.0.offset = self.state.offset;
+            err.0.line = self.state.get_line_number();
+            err.0.column = self.state.get_column_number();
+        }
+        err
+    }
+
+    /// Run `f` one array or object level deeper.
+    #[inline]
+    fn nested<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, DeError>,
+    ) -> Result<T, DeError> {
+        if self.state.enter().is_none() {
+            return Err(self.error("recursion limit exceeded"));
+        }
+        let result = f(self);
+        self.state.leave();
+        result
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Result<u8, DeError> {
+        trim_leading_whitespace_mut(&mut self.state);
+        match self.state.src_bytes.get(self.state.offset) {
+            Some(&b) => Ok(b),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    #[inline]
+    fn expect_byte(&mut self, byte: u8, message: &str) -> Result<(), DeError> {
+        if self.peek()? == byte {
+            self.state.offset += 1;
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    #[inline]
+    fn parse_literal(&mut self, lit: &'static str) -> Result<(), DeError> {
+        if self.state.src_bytes[self.state.offset..].starts_with(lit.as_bytes()) {
+            self.state.offset += lit.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", lit)))
+        }
+    }
+
+    #[inline]
+    fn parse_string(&mut self) -> Result<Cow<'a, str>, DeError> {
+        let start = self.state.offset;
+        json_string_cow(&mut self.state).ok_or_else(|| {
+            self.state.offset = start;
+            self.error("invalid string")
+        })
+    }
+
+    fn parse_number<V: Visitor<'a>>(&mut self, visitor: V) -> Result<V::Value, DeError> {
+        let Some(ns) = number_span_fast_ex(&mut self.state) else {
+            return Err(self.error("invalid number"));
+        };
+        let s = ns.span.as_str();
+        if ns.is_integer {
+            // Integers that overflow 64 bits fall through to f64.
+            if s.starts_with('-') {
+                if let Ok(n) = s.parse::<i64>() {
+                    return visitor.visit_i64(n);
+                }
+            } else if let Ok(n) = s.parse::<u64>() {
+                return visitor.visit_u64(n);
+            }
+        }
+        visitor.visit_f64(parse_json_number_f64(ns.span, ns.is_integer))
+    }
+}
+
+/// Deserialize an instance of `T` from a JSON string.
+pub fn from_str<'a, T>(src: &'a str) -> Result<T, DeError>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut de = Deserializer::new(src);
+    let value = T::deserialize(&mut de).map_err(|e| de.locate(e))?;
+    de.end()?;
+    Ok(value)
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        match self.peek()? {
+            b'n' => {
+                self.parse_literal("null")?;
+                visitor.visit_unit()
+            }
+            b't' => {
+                self.parse_literal("true")?;
+                visitor.visit_bool(true)
+            }
+            b'f' => {
+                self.parse_literal("false")?;
+                visitor.visit_bool(false)
+            }
+            b'"' => match self.parse_string()? {
+                Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+                Cow::Owned(s) => visitor.visit_string(s),
+            },
+            b'-' | b'0'..=b'9' => self.parse_number(visitor),
+            b'[' => self.nested(|de| {
+                de.state.offset += 1;
+                let value = visitor.visit_seq(SeqAccess { de, first: true })?;
+                de.expect_byte(b']', "expected `,` or `]`")?;
+                Ok(value)
+            }),
+            b'{' => self.nested(|de| {
+                de.state.offset += 1;
+                let value = visitor.visit_map(MapAccess { de, first: true })?;
+                de.expect_byte(b'}', "expected `,` or `}`")?;
+                Ok(value)
+            }),
+            _ => Err(self.error("expected value")),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.peek()? == b'n' {
+            self.parse_literal("null")?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        match self.peek()? {
+            // Unit variant: "Name"
+            b'"' => match self.parse_string()? {
+                Cow::Borrowed(s) => visitor.visit_enum(s.into_deserializer()),
+                Cow::Owned(s) => visitor.visit_enum(s.into_deserializer()),
+            },
+            // Externally tagged: {"Name": payload}
+            b'{' => self.nested(|de| {
+                de.state.offset += 1;
+                let value = visitor.visit_enum(EnumAccess { de })?;
+                de.expect_byte(b'}', "expected `}` after enum payload")?;
+                Ok(value)
+            }),
+            _ => Err(self.error("expected string or object for enum")),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        self.deserialize_any(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+// ── Compound accessors ────────────────────────────────────────
+
+struct SeqAccess<'b, 'a> {
+    de: &'b mut Deserializer<'a>,
+    first: bool,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_, 'de> {
+    type Error = DeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, DeError> {
+        match self.de.peek()? {
+            b']' => return Ok(None),
+            b',' if !self.first => self.de.state.offset += 1,
+            _ if self.first => {}
+            _ => return Err(self.de.error("expected `,` or `]`")),
+        }
+        self.first = false;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+struct MapAccess<'b, 'a> {
+    de: &'b mut Deserializer<'a>,
+    first: bool,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_, 'de> {
+    type Error = DeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, DeError> {
+        match self.de.peek()? {
+            b'}' => return Ok(None),
+            b',' if !self.first => {
+                self.de.state.offset += 1;
+                if self.de.peek()? != b'"' {
+                    return Err(self.de.error("expected string key"));
+                }
+            }
+            b'"' if self.first => {}
+            _ if self.first => return Err(self.de.error("expected string key")),
+            _ => return Err(self.de.error("expected `,` or `}`")),
+        }
+        self.first = false;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, DeError> {
+        self.de.expect_byte(b':', "expected `:`")?;
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct EnumAccess<'b, 'a> {
+    de: &'b mut Deserializer<'a>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'_, 'de> {
+    type Error = DeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), DeError> {
+        if self.de.peek()? != b'"' {
+            return Err(self.de.error("expected string key"));
+        }
+        let variant = seed.deserialize(&mut *self.de)?;
+        self.de.expect_byte(b':', "expected `:`")?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for EnumAccess<'_, 'de> {
+    type Error = DeError;
+
+    fn unit_variant(self) -> Result<(), DeError> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, DeError> {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, DeError> {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
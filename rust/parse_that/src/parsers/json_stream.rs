@@ -0,0 +1,435 @@
+// Resumable JSON event parser over chunked byte input.
+//
+// `feed()` appends bytes to an internal buffer (consumed input is compacted
+// away), `next_event()` pulls one event at a time and returns `Ok(None)` when
+// it needs more input. Tokens split across chunk boundaries — strings,
+// numbers, literals, even multi-byte UTF-8 characters — are held back until
+// complete. Whitespace-separated top-level values (NDJSON, concatenated JSON)
+// are accepted one after another.
+
+use std::borrow::Cow;
+
+use crate::leaf::trim_leading_whitespace_mut;
+use crate::parsers::json::{
+    JsonValue, json_string_fast_inner, number_span_fast_ex, parse_json_number_f64,
+    unescape_json_string,
+};
+use crate::state::{ParserState, Span};
+
+/// One step of a JSON document. Keys and scalar values borrow from the
+/// parser's buffer and are valid until the next `feed()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonEvent<'a> {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    Key(Cow<'a, str>),
+    /// A scalar: string, number, bool, or null.
+    Value(JsonValue<'a>),
+}
+
+/// Error with the absolute stream offset where it was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonStreamError {
+    pub offset: usize,
+    pub expected: &'static str,
+}
+
+impl std::fmt::Display for JsonStreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {} at offset {}", self.expected, self.offset)
+    }
+}
+
+impl std::error::Error for JsonStreamError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Array,
+    Object,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    /// A value (top level, after `:`, or after `,` in an array).
+    Value,
+    /// A value or `]` right after `[`.
+    FirstItem,
+    /// A key or `}` right after `{`.
+    FirstKey,
+    /// A key after `,` in an object.
+    Key,
+    Colon,
+    /// `,` or the closing bracket of the current container.
+    Next,
+}
+
+/// Buffer positions of a token, resolved to a borrowed event afterwards.
+enum RawEvent {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    Key(usize, usize, bool),
+    String(usize, usize, bool),
+    Number(usize, usize, bool),
+    Bool(bool),
+    Null,
+}
+
+/// Outcome of scanning a token at the buffer position.
+enum Token<T> {
+    Done(T),
+    NeedMore,
+}
+
+pub struct JsonStreamParser {
+    buf: Vec<u8>,
+    /// Consumed prefix of `buf`.
+    pos: usize,
+    /// Length of the UTF-8-valid prefix of `buf`; a split character waits past it.
+    valid: usize,
+    /// Absolute stream offset of `buf[0]`.
+    base: usize,
+    stack: Vec<Container>,
+    expect: Expect,
+    eof: bool,
+    error: Option<JsonStreamError>,
+    /// How far past `pos` an unterminated string has been scanned, so the
+    /// next feed resumes there instead of rescanning from the opening quote.
+    string_scanned: usize,
+    /// The scanned part of that string ends in an unconsumed backslash.
+    string_escape: bool,
+}
+
+impl Default for JsonStreamParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonStreamParser {
+    pub fn new() -> Self {
+        JsonStreamParser {
+            buf: Vec::new(),
+            pos: 0,
+            valid: 0,
+            base: 0,
+            stack: Vec::new(),
+            expect: Expect::Value,
+            eof: false,
+            error: None,
+            string_scanned: 0,
+            string_escape: false,
+        }
+    }
+
+    /// Append a chunk of input. Fails on invalid UTF-8.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), JsonStreamError> {
+        if let Some(err) = &self.error {
+            return Err(err.clone());
+        }
+        if self.pos > 0 {
+            self.buf.drain(..self.pos);
+            self.base += self.pos;
+            self.valid -= self.pos;
+            self.pos = 0;
+        }
+        self.buf.extend_from_slice(chunk);
+        match std::str::from_utf8(&self.buf[self.valid..]) {
+            Ok(_) => self.valid = self.buf.len(),
+            Err(e) => {
+                self.valid += e.valid_up_to();
+                if e.error_len().is_some() {
+                    return Err(self.fail(self.valid, "valid UTF-8"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Mark the end of input; pending tokens are completed or rejected.
+    pub fn finish(&mut self) {
+        self.eof = true;
+    }
+
+    /// Nesting depth of the current position.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Absolute stream offset of the next unconsumed byte.
+    pub fn offset(&self) -> usize {
+        self.base + self.pos
+    }
+
+    /// Pull the next event. `Ok(None)` means more input is needed — or, after
+    /// `finish()`, that every top-level value has been fully emitted.
+    pub fn next_event(&mut self) -> Result<Option<JsonEvent<'_>>, JsonStreamError> {
+        if let Some(err) = &self.error {
+            return Err(err.clone());
+        }
+        match self.advance()? {
+            Token::Done(raw) => Ok(Some(self.resolve(raw))),
+            Token::NeedMore => Ok(None),
+        }
+    }
+
+    #[cold]
+    fn fail(&mut self, pos: usize, expected: &'static str) -> JsonStreamError {
+        let err = JsonStreamError {
+            offset: self.base + pos,
+            expected,
+        };
+        self.error = Some(err.clone());
+        err
+    }
+
+    fn text(&self) -> &str {
+        // SAFETY: `feed` only advances `valid` over validated UTF-8.
+        unsafe { std::str::from_utf8_unchecked(&self.buf[..self.valid]) }
+    }
+
+    fn after_value(&self) -> Expect {
+        if self.stack.is_empty() {
+            Expect::Value
+        } else {
+            Expect::Next
+        }
+    }
+
+    fn close(&mut self, container: Container) -> RawEvent {
+        self.stack.pop();
+        self.pos += 1;
+        self.expect = self.after_value();
+        match container {
+            Container::Array => RawEvent::EndArray,
+            Container::Object => RawEvent::EndObject,
+        }
+    }
+
+    fn advance(&mut self) -> Result<Token<RawEvent>, JsonStreamError> {
+        loop {
+            self.pos = {
+                let mut state = ParserState::new(self.text());
+                state.offset = self.pos;
+                trim_leading_whitespace_mut(&mut state);
+                state.offset
+            };
+
+            let Some(&b) = self.buf[..self.valid].get(self.pos) else {
+                if !self.eof {
+                    return Ok(Token::NeedMore);
+                }
+                if self.valid < self.buf.len() {
+                    return Err(self.fail(self.valid, "valid UTF-8"));
+                }
+                if self.stack.is_empty() && self.expect == Expect::Value {
+                    return Ok(Token::NeedMore);
+                }
+                return Err(self.fail(self.pos, "more input"));
+            };
+            let top = self.stack.last().copied();
+
+            match self.expect {
+                Expect::Colon => {
+                    if b != b':' {
+                        return Err(self.fail(self.pos, "`:`"));
+                    }
+                    self.pos += 1;
+                    self.expect = Expect::Value;
+                }
+                Expect::Next => match (b, top) {
+                    (b',', Some(container)) => {
+                        self.pos += 1;
+                        self.expect = match container {
+                            Container::Array => Expect::Value,
+                            Container::Object => Expect::Key,
+                        };
+                    }
+                    (b']', Some(Container::Array)) => {
+                        return Ok(Token::Done(self.close(Container::Array)));
+                    }
+                    (b'}', Some(Container::Object)) => {
+                        return Ok(Token::Done(self.close(Container::Object)));
+                    }
+                    _ => return Err(self.fail(self.pos, "`,` or closing bracket")),
+                },
+                Expect::FirstKey if b == b'}' => {
+                    return Ok(Token::Done(self.close(Container::Object)));
+                }
+                Expect::FirstKey | Expect::Key => {
+                    if b != b'"' {
+                        return Err(self.fail(self.pos, "string key"));
+                    }
+                    return Ok(match self.scan_string()? {
+                        Token::Done((start, end, escaped)) => {
+                            self.expect = Expect::Colon;
+                            Token::Done(RawEvent::Key(start, end, escaped))
+                        }
+                        Token::NeedMore => Token::NeedMore,
+                    });
+                }
+                Expect::FirstItem if b == b']' => {
+                    return Ok(Token::Done(self.close(Container::Array)));
+                }
+                Expect::FirstItem | Expect::Value => return self.scan_value(b),
+            }
+        }
+    }
+
+    fn scan_value(&mut self, b: u8) -> Result<Token<RawEvent>, JsonStreamError> {
+        let token = match b {
+            b'{' | b'[' => {
+                self.pos += 1;
+                let (container, expect, event) = if b == b'{' {
+                    (Container::Object, Expect::FirstKey, RawEvent::StartObject)
+                } else {
+                    (Container::Array, Expect::FirstItem, RawEvent::StartArray)
+                };
+                self.stack.push(container);
+                self.expect = expect;
+                return Ok(Token::Done(event));
+            }
+            b'"' => match self.scan_string()? {
+                Token::Done((start, end, escaped)) => {
+                    Token::Done(RawEvent::String(start, end, escaped))
+                }
+                Token::NeedMore => Token::NeedMore,
+            },
+            b'-' | b'0'..=b'9' => self.scan_number()?,
+            b't' => self.scan_literal(b"true", RawEvent::Bool(true))?,
+            b'f' => self.scan_literal(b"false", RawEvent::Bool(false))?,
+            b'n' => self.scan_literal(b"null", RawEvent::Null)?,
+            _ => return Err(self.fail(self.pos, "value")),
+        };
+        if matches!(token, Token::Done(_)) {
+            self.expect = self.after_value();
+        }
+        Ok(token)
+    }
+
+    /// A string is complete once its closing quote is buffered; only then is
+    /// the scanner's verdict final.
+    fn scan_string(&mut self) -> Result<Token<(usize, usize, bool)>, JsonStreamError> {
+        let bytes = &self.buf[..self.valid];
+        let mut i = self.pos + self.string_scanned.max(1);
+        let mut escape = self.string_escape;
+        let closed = loop {
+            if escape {
+                if i == bytes.len() {
+                    break false;
+                }
+                i += 1;
+                escape = false;
+            }
+            match memchr::memchr2(b'"', b'\\', &bytes[i..]) {
+                Some(p) if bytes[i + p] == b'\\' => {
+                    i += p + 1;
+                    escape = true;
+                }
+                Some(_) => break true,
+                None => {
+                    i = bytes.len();
+                    break false;
+                }
+            }
+        };
+        self.string_scanned = i - self.pos;
+        self.string_escape = escape;
+        if !closed {
+            if self.eof {
+                let expected = if self.valid < self.buf.len() {
+                    "valid UTF-8"
+                } else {
+                    "closing quote"
+                };
+                return Err(self.fail(self.valid, expected));
+            }
+            return Ok(Token::NeedMore);
+        }
+
+        let mut state = ParserState::new(self.text());
+        state.offset = self.pos;
+        let scanned = json_string_fast_inner(&mut state, false).map(|(span, escaped)| {
+            (span.start, span.end, escaped, state.offset)
+        });
+        drop(state);
+        let Some((start, end, escaped, offset)) = scanned else {
+            return Err(self.fail(self.pos, "valid escape sequence"));
+        };
+        self.pos = offset;
+        self.string_scanned = 0;
+        self.string_escape = false;
+        Ok(Token::Done((start, end, escaped)))
+    }
+
+    /// A number is complete once a non-number byte (or end of input) follows it.
+    fn scan_number(&mut self) -> Result<Token<RawEvent>, JsonStreamError> {
+        let bytes = &self.buf[..self.valid];
+        let run = bytes[self.pos..]
+            .iter()
+            .position(|b| !matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E'));
+        let Some(run) = run.or(self.eof.then(|| bytes.len() - self.pos)) else {
+            return Ok(Token::NeedMore);
+        };
+
+        let mut state = ParserState::new(self.text());
+        state.offset = self.pos;
+        let scanned = number_span_fast_ex(&mut state)
+            .map(|ns| (ns.span.start, ns.span.end, ns.is_integer));
+        drop(state);
+        // The span must cover the whole run: `007` is one bad number, not three.
+        let Some((start, end, is_integer)) = scanned.filter(|&(_, end, _)| end == self.pos + run)
+        else {
+            return Err(self.fail(self.pos, "number"));
+        };
+        self.pos = end;
+        Ok(Token::Done(RawEvent::Number(start, end, is_integer)))
+    }
+
+    fn scan_literal(
+        &mut self,
+        literal: &'static [u8],
+        event: RawEvent,
+    ) -> Result<Token<RawEvent>, JsonStreamError> {
+        let rest = &self.buf[self.pos..self.valid];
+        if rest.starts_with(literal) {
+            self.pos += literal.len();
+            return Ok(Token::Done(event));
+        }
+        if !self.eof && literal.starts_with(rest) {
+            return Ok(Token::NeedMore);
+        }
+        Err(self.fail(self.pos, "value"))
+    }
+
+    fn resolve(&self, raw: RawEvent) -> JsonEvent<'_> {
+        let text = self.text();
+        let string = |start: usize, end: usize, escaped: bool| {
+            let s = &text[start..end];
+            if escaped {
+                // Already validated by the scanner
+                Cow::Owned(unescape_json_string(s).unwrap_or_default())
+            } else {
+                Cow::Borrowed(s)
+            }
+        };
+        match raw {
+            RawEvent::StartObject => JsonEvent::StartObject,
+            RawEvent::EndObject => JsonEvent::EndObject,
+            RawEvent::StartArray => JsonEvent::StartArray,
+            RawEvent::EndArray => JsonEvent::EndArray,
+            RawEvent::Key(start, end, escaped) => JsonEvent::Key(string(start, end, escaped)),
+            RawEvent::String(start, end, escaped) => {
+                JsonEvent::Value(JsonValue::String(string(start, end, escaped)))
+            }
+            RawEvent::Number(start, end, is_integer) => JsonEvent::Value(JsonValue::Number(
+                parse_json_number_f64(Span::new(start, end, text), is_integer),
+            )),
+            RawEvent::Bool(b) => JsonEvent::Value(JsonValue::Bool(b)),
+            RawEvent::Null => JsonEvent::Value(JsonValue::Null),
+        }
+    }
+}
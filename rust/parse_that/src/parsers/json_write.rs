@@ -0,0 +1,175 @@
+// JSON writer for `JsonValue`: the inverse of `json_parser()`.
+//
+// Strings are re-escaped (only `"`, `\` and control characters; everything
+// else is written as UTF-8), and numbers use Rust's shortest round-trip float
+// formatting, switching to exponent form outside `[1e-6, 1e21)` like
+// JavaScript does. Non-finite numbers have no JSON form and are written as
+// `null`.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use crate::parsers::json::{JsonValue, find_string_special};
+
+/// Output layout for the JSON writer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonFormat {
+    /// No whitespace at all.
+    #[default]
+    Compact,
+    /// One member per line, nested `n` spaces per level.
+    Indented(usize),
+}
+
+const SPACES: &[u8; 64] = &[b' '; 64];
+
+fn write_indent<W: Write>(w: &mut W, mut width: usize) -> io::Result<()> {
+    w.write_all(b"\n")?;
+    while width > 0 {
+        let n = width.min(SPACES.len());
+        w.write_all(&SPACES[..n])?;
+        width -= n;
+    }
+    Ok(())
+}
+
+/// Write `s` as a quoted JSON string. Unescaped runs are copied in bulk.
+pub fn write_json_string<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    let bytes = s.as_bytes();
+    w.write_all(b"\"")?;
+    let mut start = 0;
+    while let Some(i) = find_string_special(bytes, start) {
+        w.write_all(&bytes[start..i])?;
+        match bytes[i] {
+            b'"' => w.write_all(b"\\\"")?,
+            b'\\' => w.write_all(b"\\\\")?,
+            b'\n' => w.write_all(b"\\n")?,
+            b'\r' => w.write_all(b"\\r")?,
+            b'\t' => w.write_all(b"\\t")?,
+            0x08 => w.write_all(b"\\b")?,
+            0x0C => w.write_all(b"\\f")?,
+            c => write!(w, "\\u{c:04x}")?,
+        }
+        start = i + 1;
+    }
+    w.write_all(&bytes[start..])?;
+    w.write_all(b"\"")
+}
+
+/// Write `n` in its shortest form that parses back to the same `f64`.
+pub fn write_json_number<W: Write>(w: &mut W, n: f64) -> io::Result<()> {
+    if !n.is_finite() {
+        return w.write_all(b"null");
+    }
+    let abs = n.abs();
+    if abs != 0.0 && !(1e-6..1e21).contains(&abs) {
+        write!(w, "{n:e}")
+    } else {
+        write!(w, "{n}")
+    }
+}
+
+/// Write `[...]`, laying out each element with `write_item(w, item, depth)`.
+pub(crate) fn write_array<W: Write, T>(
+    w: &mut W,
+    items: &[T],
+    format: JsonFormat,
+    depth: usize,
+    write_item: impl Fn(&mut W, &T, usize) -> io::Result<()>,
+) -> io::Result<()> {
+    if items.is_empty() {
+        return w.write_all(b"[]");
+    }
+    w.write_all(b"[")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            w.write_all(b",")?;
+        }
+        if let JsonFormat::Indented(n) = format {
+            write_indent(w, n * (depth + 1))?;
+        }
+        write_item(w, item, depth + 1)?;
+    }
+    if let JsonFormat::Indented(n) = format {
+        write_indent(w, n * depth)?;
+    }
+    w.write_all(b"]")
+}
+
+/// Write `{...}`, laying out each member value with `write_item(w, item, depth)`.
+pub(crate) fn write_object<W: Write, T>(
+    w: &mut W,
+    entries: &[(Cow<'_, str>, T)],
+    format: JsonFormat,
+    depth: usize,
+    write_item: impl Fn(&mut W, &T, usize) -> io::Result<()>,
+) -> io::Result<()> {
+    if entries.is_empty() {
+        return w.write_all(b"{}");
+    }
+    w.write_all(b"{")?;
+    for (i, (key, item)) in entries.iter().enumerate() {
+        if i > 0 {
+            w.write_all(b",")?;
+        }
+        if let JsonFormat::Indented(n) = format {
+            write_indent(w, n * (depth + 1))?;
+        }
+        write_json_string(w, key)?;
+        w.write_all(match format {
+            JsonFormat::Compact => b":",
+            JsonFormat::Indented(_) => b": ",
+        })?;
+        write_item(w, item, depth + 1)?;
+    }
+    if let JsonFormat::Indented(n) = format {
+        write_indent(w, n * depth)?;
+    }
+    w.write_all(b"}")
+}
+
+fn write_value<W: Write>(
+    w: &mut W,
+    value: &JsonValue<'_>,
+    format: JsonFormat,
+    depth: usize,
+) -> io::Result<()> {
+    let item = |w: &mut W, v: &JsonValue<'_>, depth| write_value(w, v, format, depth);
+    match value {
+        JsonValue::Null => w.write_all(b"null"),
+        JsonValue::Bool(true) => w.write_all(b"true"),
+        JsonValue::Bool(false) => w.write_all(b"false"),
+        JsonValue::Number(n) => write_json_number(w, *n),
+        JsonValue::String(s) => write_json_string(w, s),
+        JsonValue::Array(items) => write_array(w, items, format, depth, item),
+        JsonValue::Object(entries) => write_object(w, entries, format, depth, item),
+    }
+}
+
+/// Collect writer output into a `String`.
+pub(crate) fn write_to_string(write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+    let mut buf = Vec::new();
+    write(&mut buf).expect("writing to a Vec cannot fail");
+    // SAFETY: only `str` contents and ASCII are ever written.
+    unsafe { String::from_utf8_unchecked(buf) }
+}
+
+impl JsonValue<'_> {
+    /// Serialize as compact JSON.
+    pub fn to_json_string(&self) -> String {
+        self.to_json_string_with(JsonFormat::Compact)
+    }
+
+    pub fn to_json_string_with(&self, format: JsonFormat) -> String {
+        write_to_string(|buf| write_value(buf, self, format, 0))
+    }
+
+    /// Write compact JSON to `w`. Wrap unbuffered sinks in a `BufWriter`.
+    pub fn write_json<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_json_with(w, JsonFormat::Compact)
+    }
+
+    pub fn write_json_with<W: Write>(&self, w: &mut W, format: JsonFormat) -> io::Result<()> {
+        write_value(w, self, format, 0)
+    }
+}
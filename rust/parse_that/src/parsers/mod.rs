@@ -1,6 +1,16 @@
 pub mod json;
 pub use json::number_span_fast_parser;
 
+pub mod json5;
+pub mod json_cursor;
+pub mod json_lossless;
+pub mod json_path;
+pub mod json_stream;
+pub mod json_write;
+
+#[cfg(feature = "serde")]
+pub mod json_serde;
+
 pub mod css;
 pub mod csv;
 pub mod utils;
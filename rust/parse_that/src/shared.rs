@@ -0,0 +1,120 @@
+#[cfg(feature = "sync")]
+use crate::parse::{ParseError, Parser};
+
+// ── Thread-safe parsers (`sync` feature) ──────────────────────
+//
+// With `sync`, every parser function is `Send + Sync`, so a `Parser` can be
+// built once and shared between threads. User closures then need the same
+// bounds, and lazy bodies are built in thread-safe cells. Results a parse
+// caches (memo tables, left-recursion seeds) live on its `ParserState`, so
+// threads sharing a parser never contend on them. Without the feature nothing
+// changes.
+
+/// `Send + Sync` with the `sync` feature, implemented by every type without.
+/// Bounds user closures stored in parsers.
+#[cfg(feature = "sync")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "sync")]
+impl<T: Send + Sync + ?Sized> MaybeSync for T {}
+
+/// `Send + Sync` with the `sync` feature, implemented by every type without.
+/// Bounds user closures stored in parsers.
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+#[cfg(feature = "sync")]
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+/// One of this crate's grammars, whose parser can be built once and then
+/// used on inputs of any lifetime; see [`SyncParser`].
+///
+/// `Parser<'a, _>` is tied to one input lifetime, and `SyncParser` reuses the
+/// `'static` instance for shorter ones. That is sound only if the parser never
+/// returns, from one parse, a value it kept from an earlier parse. The crate's
+/// own grammars keep nothing between parses (memo tables live on the
+/// `ParserState`), but state captured by user closures cannot be checked, so
+/// the trait is sealed. To share your own grammar, build it once and pass it
+/// by reference to scoped threads: `Parser` is `Sync` with this feature.
+///
+/// ```compile_fail,E0277
+/// use parse_that::*;
+///
+/// struct Words;
+///
+/// impl Grammar for Words {
+///     type Output<'a> = Vec<&'a str>;
+///
+///     fn build<'a>() -> Parser<'a, Vec<&'a str>> {
+///         regex("[a-z]+").trim_whitespace().many(..)
+///     }
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait Grammar: sealed::Sealed + 'static {
+    type Output<'a>: 'a;
+
+    fn build<'a>() -> Parser<'a, Self::Output<'a>>;
+}
+
+/// A parser built once and shared: it can live in a `static` and be called
+/// from many threads at once, on inputs of any lifetime.
+///
+/// ```
+/// use std::sync::LazyLock;
+/// use parse_that::parsers::json::JsonGrammar;
+/// use parse_that::*;
+///
+/// static JSON: LazyLock<SyncParser<JsonGrammar>> = LazyLock::new(SyncParser::new);
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|i| std::thread::spawn(move || {
+///         let src = format!("[{i}, {{\"a\": true}}]");
+///         JSON.parse(&src).is_some()
+///     }))
+///     .collect();
+/// assert!(handles.into_iter().all(|h| h.join().unwrap()));
+/// ```
+#[cfg(feature = "sync")]
+pub struct SyncParser<G: Grammar> {
+    parser: Parser<'static, G::Output<'static>>,
+}
+
+#[cfg(feature = "sync")]
+impl<G: Grammar> SyncParser<G> {
+    pub fn new() -> Self {
+        SyncParser { parser: G::build() }
+    }
+
+    /// The parser, at the lifetime of the input it will be called on.
+    #[inline]
+    pub fn get<'a>(&self) -> &Parser<'a, G::Output<'a>> {
+        let parser: *const Parser<'static, G::Output<'static>> = &self.parser;
+        // SAFETY: `G::build` is generic over the input lifetime, so the two
+        // instantiations have the same layout and behavior, and `Grammar` is
+        // sealed to this crate's grammars, which keep nothing borrowed from
+        // one input past its parse.
+        unsafe { &*parser.cast::<Parser<'a, G::Output<'a>>>() }
+    }
+
+    #[inline]
+    pub fn parse<'a>(&self, src: &'a str) -> Option<G::Output<'a>> {
+        self.get().parse(src)
+    }
+
+    pub fn parse_or_error<'a>(&self, src: &'a str) -> Result<G::Output<'a>, ParseError> {
+        self.get().parse_or_error(src)
+    }
+}
+
+#[cfg(feature = "sync")]
+impl<G: Grammar> Default for SyncParser<G> {
+    fn default() -> Self {
+        SyncParser::new()
+    }
+}
@@ -1,7 +1,8 @@
 use aho_corasick::{AhoCorasickBuilder, MatchKind, StartKind};
 
-use crate::leaf::cached_regex;
+use crate::leaf::cached_anchored_regex;
 use crate::parse::ParserFn;
+use crate::shared::MaybeSync;
 use crate::state::Span;
 
 use super::{SpanKind, SpanParser, SpanScanner};
@@ -25,7 +26,7 @@ pub fn sp_string<'a>(s: &'static str) -> SpanParser<'a> {
 
 /// Match regex pattern. Uses global cache to avoid recompilation.
 pub fn sp_regex<'a>(r: &str) -> SpanParser<'a> {
-    let re = cached_regex(r);
+    let re = cached_anchored_regex(r);
     #[cfg(feature = "diagnostics")]
     {
         let label: &'static str = Box::leak(format!("/{}/", r).into_boxed_str());
@@ -63,7 +64,7 @@ pub fn sp_take_while_byte<'a>(f: fn(u8) -> bool) -> SpanParser<'a> {
 
 /// Take characters while predicate holds (char-level, Unicode-safe).
 #[inline]
-pub fn sp_take_while_char<'a>(f: impl Fn(char) -> bool + 'a) -> SpanParser<'a> {
+pub fn sp_take_while_char<'a>(f: impl Fn(char) -> bool + MaybeSync + 'a) -> SpanParser<'a> {
     sp_new!(SpanKind::TakeWhileChar(Box::new(f)), "matching character")
 }
 
@@ -99,6 +100,30 @@ pub fn sp_json_string_quoted<'a>() -> SpanParser<'a> {
     sp_new!(SpanKind::Scanner(SpanScanner::JsonStringQuoted), "string")
 }
 
+/// Monolithic JSON5 number scanner (hex, `Infinity`, `NaN`, explicit `+`).
+#[inline]
+pub fn sp_json5_number<'a>() -> SpanParser<'a> {
+    sp_new!(SpanKind::Scanner(SpanScanner::Json5Number), "number")
+}
+
+/// Monolithic JSON5 string scanner (single or double quotes). Content span.
+#[inline]
+pub fn sp_json5_string<'a>() -> SpanParser<'a> {
+    sp_new!(SpanKind::Scanner(SpanScanner::Json5String), "string")
+}
+
+/// Monolithic JSON5 identifier scanner (unquoted object keys).
+#[inline]
+pub fn sp_json5_identifier<'a>() -> SpanParser<'a> {
+    sp_new!(SpanKind::Scanner(SpanScanner::Json5Identifier), "identifier")
+}
+
+/// Monolithic JSON5 whitespace + comment scanner.
+#[inline]
+pub fn sp_json5_ws_comment<'a>() -> SpanParser<'a> {
+    sp_new!(SpanKind::Scanner(SpanScanner::Json5WsComment))
+}
+
 /// Monolithic CSS identifier scanner — direct byte scanning.
 #[inline]
 pub fn sp_css_ident<'a>() -> SpanParser<'a> {
@@ -2,6 +2,7 @@
 
 use super::{SpanKind, SpanParser};
 use crate::parse::Parser;
+use crate::shared::MaybeSync;
 use crate::state::{ParserState, Span};
 use crate::utils::extract_bounds;
 use std::ops::RangeBounds;
@@ -132,6 +133,32 @@ impl<'a> SpanParser<'a> {
         sp_new!(SpanKind::Peek(Box::new(self)))
     }
 
+    /// Packrat memoization, cached on the `ParserState` like `Parser::memoize()`.
+    #[inline]
+    pub fn memoize_span(self) -> SpanParser<'a> {
+        sp_new!(SpanKind::Memoize(Box::new(self), crate::state::MemoId::new()))
+    }
+
+    /// Commit to `self`; see `Parser::cut`.
+    #[inline]
+    pub fn cut_span(self) -> SpanParser<'a> {
+        sp_new!(SpanKind::Cut(Box::new(self)))
+    }
+
+    /// Report failures as `expected <name>`; see `Parser::label`.
+    #[cfg(feature = "diagnostics")]
+    #[inline]
+    pub fn label(self, name: &'static str) -> SpanParser<'a> {
+        sp_new!(SpanKind::Labeled(Box::new(self)), name)
+    }
+
+    /// No-op without the `diagnostics` feature.
+    #[cfg(not(feature = "diagnostics"))]
+    #[inline]
+    pub fn label(self, _name: &'static str) -> SpanParser<'a> {
+        self
+    }
+
     // ── Flag setters ──────────────────────────────────────────
 
     #[inline]
@@ -162,7 +189,10 @@ impl<'a> SpanParser<'a> {
 
     /// Map with a closure (not just fn pointer).
     #[inline]
-    pub fn map_closure<O: 'a>(self, f: impl Fn(Span<'a>) -> O + 'a) -> Parser<'a, O> {
+    pub fn map_closure<O: 'a>(
+        self,
+        f: impl Fn(Span<'a>) -> O + MaybeSync + 'a,
+    ) -> Parser<'a, O> {
         Parser::new(move |state: &mut ParserState<'a>| self.call(state).map(&f))
     }
 }
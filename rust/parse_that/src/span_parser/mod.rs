@@ -1,7 +1,8 @@
-use regex::Regex;
 use std::sync::Arc;
 
-use crate::leaf::{trim_leading_whitespace, trim_leading_whitespace_mut};
+use crate::leaf::{
+    AnchoredRegex, anchored_match, trim_leading_whitespace, trim_leading_whitespace_mut,
+};
 use crate::parse::ParserFn;
 use crate::state::{ParserState, Span};
 
@@ -56,13 +57,18 @@ pub struct SpanParser<'a> {
     pub(super) label: Option<&'static str>,
 }
 
+#[cfg(not(feature = "sync"))]
+type CharPredicate<'a> = Box<dyn Fn(char) -> bool + 'a>;
+#[cfg(feature = "sync")]
+type CharPredicate<'a> = Box<dyn Fn(char) -> bool + Send + Sync + 'a>;
+
 pub(super) enum SpanKind<'a> {
     // === Leaves (no inner parser, no vtable) ===
     StringLiteral(&'static [u8]),
-    RegexMatch(Arc<Regex>),
+    RegexMatch(Arc<AnchoredRegex>),
     AhoCorasickMatch(AhoCorasick),
     TakeWhileByte(fn(u8) -> bool),
-    TakeWhileChar(Box<dyn Fn(char) -> bool + 'a>),
+    TakeWhileChar(CharPredicate<'a>),
     NextN(usize),
     Epsilon,
     /// Fast path for negated byte classes with one excluded byte.
@@ -127,6 +133,15 @@ pub(super) enum SpanKind<'a> {
     Peek(Box<SpanParser<'a>>),
     /// End-of-input check: succeeds (empty Span) if at end of source.
     Eof,
+    /// Packrat memoization: caches the span and end offset (or failure) per
+    /// start offset.
+    Memoize(Box<SpanParser<'a>>, crate::state::MemoId),
+    /// Failure of inner is a hard error; see `Parser::cut`.
+    Cut(Box<SpanParser<'a>>),
+    /// Failure of inner without consuming input reports this parser's label
+    /// instead of inner's; see `Parser::label`.
+    #[cfg(feature = "diagnostics")]
+    Labeled(Box<SpanParser<'a>>),
 
     // === Escape hatch ===
     Boxed(Box<dyn ParserFn<'a, Span<'a>> + 'a>),
@@ -165,12 +180,12 @@ impl<'a> SpanParser<'a> {
 
         let result = self.call_inner(state);
 
-        if let Some(cp) = checkpoint {
-            if result.is_none() {
-                state.furthest_offset = state.furthest_offset.max(state.offset);
-                state.offset = cp;
-                return None;
-            }
+        if let Some(cp) = checkpoint
+            && result.is_none()
+        {
+            state.furthest_offset = state.furthest_offset.max(state.offset);
+            state.offset = cp;
+            return None;
         }
         // Skip post-trim on failure
         if result.is_some() && self.flags & FLAG_TRIM_WS != 0 {
@@ -206,13 +221,13 @@ impl<'a> SpanParser<'a> {
 
             SpanKind::RegexMatch(re) => {
                 let slc = state.src.get(state.offset..)?;
-                match re.find_at(slc, 0) {
-                    Some(m) if m.start() == 0 => {
+                match anchored_match(re, slc) {
+                    Some(len) => {
                         let start = state.offset;
-                        state.offset += m.end();
+                        state.offset += len;
                         Some(Span::new(start, state.offset, state.src))
                     }
-                    _ => {
+                    None => {
                         #[cfg(feature = "diagnostics")]
                         if let Some(lbl) = self.label {
                             state.add_expected(lbl);
@@ -307,10 +322,10 @@ impl<'a> SpanParser<'a> {
             SpanKind::Scanner(scanner) => {
                 let result = scanner.call(state);
                 #[cfg(feature = "diagnostics")]
-                if result.is_none() {
-                    if let Some(lbl) = self.label {
-                        state.add_expected(lbl);
-                    }
+                if result.is_none()
+                    && let Some(lbl) = self.label
+                {
+                    state.add_expected(lbl);
                 }
                 result
             }
@@ -477,6 +492,9 @@ impl<'a> SpanParser<'a> {
                     }
                     state.furthest_offset = state.furthest_offset.max(state.offset);
                     state.offset = cp;
+                    if state.is_cut() {
+                        return None;
+                    }
                 }
                 None
             }
@@ -498,6 +516,9 @@ impl<'a> SpanParser<'a> {
                         }
                         None => {
                             state.offset = prev_offset;
+                            if state.is_cut() {
+                                return None;
+                            }
                             break;
                         }
                     }
@@ -514,7 +535,7 @@ impl<'a> SpanParser<'a> {
                 let mut count = 0;
                 // Parse first element
                 let Some(first_span) = inner.call(state) else {
-                    if *lo == 0 {
+                    if *lo == 0 && !state.is_cut() {
                         return Some(Span::new(start, start, state.src));
                     }
                     return None;
@@ -539,6 +560,9 @@ impl<'a> SpanParser<'a> {
                         break;
                     }
                 }
+                if state.is_cut() {
+                    return None;
+                }
                 if count >= *lo {
                     Some(Span::new(start, end, state.src))
                 } else {
@@ -553,7 +577,7 @@ impl<'a> SpanParser<'a> {
                 trim_leading_whitespace_mut(state);
                 // Parse first element
                 if inner.call(state).is_none() {
-                    if *lo == 0 {
+                    if *lo == 0 && !state.is_cut() {
                         return Some(Span::new(start, state.offset, state.src));
                     }
                     return None;
@@ -576,6 +600,9 @@ impl<'a> SpanParser<'a> {
                         break;
                     }
                 }
+                if state.is_cut() {
+                    return None;
+                }
                 if count >= *lo {
                     // Post-trim after the last element
                     trim_leading_whitespace_mut(state);
@@ -588,6 +615,9 @@ impl<'a> SpanParser<'a> {
             SpanKind::Opt(inner) => {
                 let start = state.offset;
                 if inner.call(state).is_none() {
+                    if state.is_cut() {
+                        return None;
+                    }
                     return Some(Span::new(start, start, state.src));
                 }
                 Some(Span::new(start, state.offset, state.src))
@@ -645,7 +675,7 @@ impl<'a> SpanParser<'a> {
                 let span = main.call(state)?;
                 let checkpoint = state.offset;
                 let saved_furthest = state.furthest_offset;
-                if negated.call(state).is_none() {
+                if negated.call(state).is_none() && !state.is_cut() {
                     state.offset = checkpoint;
                     state.furthest_offset = saved_furthest;
                     return Some(span);
@@ -658,7 +688,7 @@ impl<'a> SpanParser<'a> {
             SpanKind::Minus(main, excluded) => {
                 let checkpoint = state.offset;
                 let saved_furthest = state.furthest_offset;
-                if excluded.call(state).is_some() {
+                if excluded.call(state).is_some() || state.is_cut() {
                     state.offset = checkpoint;
                     state.furthest_offset = saved_furthest;
                     return None;
@@ -680,7 +710,7 @@ impl<'a> SpanParser<'a> {
             SpanKind::Negate(inner) => {
                 let checkpoint = state.offset;
                 let saved_furthest = state.furthest_offset;
-                if inner.call(state).is_none() {
+                if inner.call(state).is_none() && !state.is_cut() {
                     state.offset = checkpoint;
                     state.furthest_offset = saved_furthest;
                     return Some(Span::new(checkpoint, checkpoint, state.src));
@@ -711,6 +741,45 @@ impl<'a> SpanParser<'a> {
                 }
             }
 
+            SpanKind::Memoize(inner, id) => {
+                let start = state.offset;
+                let capacity = crate::state::MEMO_DEFAULT_CAPACITY;
+                if let Some(entry) = state.memo.table::<Span<'a>>(*id, capacity).get(start) {
+                    let (end, span) = entry?;
+                    state.offset = end;
+                    return Some(span);
+                }
+                let result = inner.call(state);
+                if result.is_none() && state.is_cut() {
+                    return None;
+                }
+                let entry = result.map(|span| (state.offset, span));
+                state.memo.table(*id, capacity).insert(start, entry);
+                result
+            }
+
+            SpanKind::Cut(inner) => {
+                let result = inner.call(state);
+                if result.is_none() && state.cut_offset.is_none() {
+                    state.cut_offset = Some(state.offset);
+                }
+                result
+            }
+
+            #[cfg(feature = "diagnostics")]
+            SpanKind::Labeled(inner) => {
+                let start = state.offset;
+                let prev_furthest = state.furthest_offset;
+                let prev_expected = state.expected.len();
+                let result = inner.call(state);
+                if result.is_none()
+                    && let Some(lbl) = self.label
+                {
+                    state.relabel_expected(start, prev_furthest, prev_expected, lbl);
+                }
+                result
+            }
+
             SpanKind::Boxed(inner) => inner.call(state),
         }
     }
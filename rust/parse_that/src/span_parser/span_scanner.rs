@@ -12,6 +12,16 @@ pub enum SpanScanner {
     /// Like JsonString but returns span including quote delimiters (for BBNF codegen).
     JsonStringQuoted,
 
+    // JSON5 scanners
+    /// [+-](Infinity | NaN | 0x hex | decimal with optional leading/trailing `.`)
+    Json5Number,
+    /// "..." or '...' with JSON5 escapes. Returns content span (exclusive of quotes).
+    Json5String,
+    /// ECMAScript IdentifierName (unquoted object key).
+    Json5Identifier,
+    /// (\s | //... | /\*...\*/)* — always succeeds unless a block comment is unterminated.
+    Json5WsComment,
+
     // CSS scanners
     /// -?[a-zA-Z_][\w-]* | --[\w-]+
     CssIdent,
@@ -30,6 +40,10 @@ impl SpanScanner {
             Self::JsonNumber => crate::parsers::json::number_span_fast(state),
             Self::JsonString => crate::parsers::json::json_string_fast(state),
             Self::JsonStringQuoted => crate::parsers::json::json_string_fast_quoted(state),
+            Self::Json5Number => crate::parsers::json5::json5_number_fast(state),
+            Self::Json5String => crate::parsers::json5::json5_string_fast(state),
+            Self::Json5Identifier => crate::parsers::json5::json5_identifier_fast(state),
+            Self::Json5WsComment => crate::parsers::json5::json5_ws_comment_fast(state),
             Self::CssIdent => crate::parsers::css::css_ident_fast(state),
             Self::CssWsComment => crate::parsers::css::css_ws_comment_fast(state),
             Self::CssString => crate::parsers::css::css_string_fast(state),
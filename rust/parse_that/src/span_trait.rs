@@ -97,6 +97,9 @@ impl<'a> ParserSpan<'a> for Parser<'a, Span<'a>> {
                     }
                     None => {
                         state.offset = prev_offset;
+                        if state.is_cut() {
+                            return None;
+                        }
                         break;
                     }
                 }
@@ -127,7 +130,7 @@ impl<'a> ParserSpan<'a> for Parser<'a, Span<'a>> {
 
             // Parse first element
             let Some(first_value) = self.call(state) else {
-                if lower_bound == 0 {
+                if lower_bound == 0 && !state.is_cut() {
                     return Some(Span::new(start, start, state.src));
                 }
                 return None;
@@ -154,6 +157,9 @@ impl<'a> ParserSpan<'a> for Parser<'a, Span<'a>> {
                 }
             }
 
+            if state.is_cut() {
+                return None;
+            }
             if count >= lower_bound {
                 Some(Span::new(start, end, state.src))
             } else {
@@ -1,7 +1,15 @@
 use pprint::Pretty;
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+use std::sync::Arc;
 #[cfg(feature = "diagnostics")]
 use smallvec::SmallVec;
 
+use crate::combinators::IndentMode;
+use crate::line_index::{LazyLineIndex, LineCol, LineIndex};
+use crate::trivia::Trivia;
+
 // ── Diagnostic types (feature-gated) ──────────────────────────
 
 #[cfg(feature = "diagnostics")]
@@ -42,6 +50,12 @@ pub struct Diagnostic {
     pub suggestions: Vec<Suggestion>,
     pub secondary_spans: Vec<SecondarySpan>,
     pub found: String,
+    /// Where a `cut()` parser failed, if the error is a committed one.
+    pub cut_offset: Option<usize>,
+    /// The user error behind the failure, if any (see `Parser::try_map`).
+    pub custom_error: Option<CustomError>,
+    /// The resource limit that stopped the parse, if any.
+    pub limit_error: Option<LimitError>,
 }
 
 #[derive(Pretty, Debug, Default, PartialEq, Clone, Copy, Hash, Eq)]
@@ -71,9 +85,346 @@ impl<'a> Span<'a> {
         );
         unsafe { self.src.get_unchecked(self.start..self.end) }
     }
+
+    /// Positions of `start` and `end`, via an index of `src`.
+    pub fn line_cols(&self, index: &LineIndex) -> (LineCol, LineCol) {
+        (index.line_col(self.start), index.line_col(self.end))
+    }
+}
+
+// ── Custom errors ─────────────────────────────────────────────
+
+/// A user-defined error raised while parsing (by `Parser::try_map` or
+/// `ParserState::record_error`), with the span of the rejected input.
+///
+/// The payload is type-erased so `ParserState` stays non-generic; recover the
+/// concrete type with `downcast_ref`. Clones share the payload, and equality
+/// is identity of the payload plus the span.
+#[derive(Debug, Clone)]
+pub struct CustomError {
+    pub start: usize,
+    pub end: usize,
+    pub error: Arc<dyn std::error::Error + Send + Sync>,
+}
+
+impl CustomError {
+    pub fn new(
+        start: usize,
+        end: usize,
+        error: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        CustomError {
+            start,
+            end,
+            error: Arc::from(error.into()),
+        }
+    }
+
+    pub fn downcast_ref<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref()
+    }
+}
+
+impl std::fmt::Display for CustomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl PartialEq for CustomError {
+    fn eq(&self, other: &Self) -> bool {
+        (self.start, self.end) == (other.start, other.end) && Arc::ptr_eq(&self.error, &other.error)
+    }
+}
+
+impl Eq for CustomError {}
+
+impl std::hash::Hash for CustomError {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.start.hash(state);
+        self.end.hash(state);
+        Arc::as_ptr(&self.error).cast::<()>().hash(state);
+    }
+}
+
+// ── Resource limits ───────────────────────────────────────────
+
+/// A resource limit that stopped a parse; see `Parser::max_depth` and
+/// `Parser::fuel`. Reported as `ParseError::limit_error` and
+/// `Diagnostic::limit_error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitError {
+    /// Recursive parsers nested deeper than `max_depth`.
+    Depth,
+    /// The step budget ran out.
+    Fuel,
+}
+
+impl std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitError::Depth => write!(f, "nesting depth limit exceeded"),
+            LimitError::Fuel => write!(f, "step budget exhausted"),
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+// ── User context ──────────────────────────────────────────────
+
+trait ContextValue: Any {
+    fn clone_rc(&self) -> Rc<dyn ContextValue>;
+    fn type_name(&self) -> &'static str;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any>;
+}
+
+impl<T: Any + Clone> ContextValue for T {
+    fn clone_rc(&self) -> Rc<dyn ContextValue> {
+        Rc::new(self.clone())
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+}
+
+/// User-defined context carried by a `ParserState`: an indentation stack, a
+/// symbol table of typedef names, a pending heredoc terminator, ...
+///
+/// Any `Clone + 'static` type can be stored. Cloning a `UserContext` shares
+/// the value; it is copied on the first write while shared. Alternations
+/// (`|`, `alt!`, `one_of`) and `save_state()` hold such a clone as their
+/// checkpoint, so a branch that updates the context copies it once. Large
+/// contexts updated inside alternations should still be cheap to clone
+/// (`Rc`, persistent collections). Equality and hashing only consider its type.
+// `Rc<dyn ContextValue>` is itself `Any + Clone`, so `ContextValue` methods
+// are called on `*self.0` to reach the stored value.
+pub struct UserContext(Rc<dyn ContextValue>);
+
+impl UserContext {
+    pub fn new<T: Any + Clone>(value: T) -> Self {
+        UserContext(Rc::new(value))
+    }
+
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        (*self.0).as_any().downcast_ref()
+    }
+
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        if !(*self.0).as_any().is::<T>() {
+            return None;
+        }
+        if Rc::get_mut(&mut self.0).is_none() {
+            self.0 = (*self.0).clone_rc();
+        }
+        Rc::get_mut(&mut self.0)?.as_any_mut().downcast_mut()
+    }
+
+    pub fn downcast<T: Any + Clone>(self) -> Result<T, Self> {
+        if (*self.0).as_any().is::<T>() {
+            Ok(Rc::unwrap_or_clone(self.0.into_any().downcast().unwrap()))
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Whether `a` and `b` are the same shared value.
+    #[inline(always)]
+    fn same(a: &Option<UserContext>, b: &UserContext) -> bool {
+        a.as_ref().is_some_and(|a| Rc::ptr_eq(&a.0, &b.0))
+    }
+}
+
+impl Clone for UserContext {
+    fn clone(&self) -> Self {
+        UserContext(self.0.clone())
+    }
+}
+
+impl std::fmt::Debug for UserContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UserContext<{}>", (*self.0).type_name())
+    }
+}
+
+impl PartialEq for UserContext {
+    fn eq(&self, other: &Self) -> bool {
+        (*self.0).as_any().type_id() == (*other.0).as_any().type_id()
+    }
+}
+
+impl Eq for UserContext {}
+
+impl std::hash::Hash for UserContext {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (*self.0).as_any().type_id().hash(state);
+    }
+}
+
+// ── Packrat memoization ───────────────────────────────────────
+
+static NEXT_MEMO_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Names the memo table of one memoized parser within a parse's
+/// `MemoTables`. Drawn once when the parser is built.
+#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
+pub(crate) struct MemoId(usize);
+
+impl MemoId {
+    pub(crate) fn new() -> Self {
+        MemoId(NEXT_MEMO_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
+    }
+}
+
+/// Default bound on the entries one memoized parser keeps per parse.
+pub const MEMO_DEFAULT_CAPACITY: usize = 1 << 16;
+
+/// Packrat cache of one memoized parser for one parse. Entries map an offset
+/// to `None` (failed) or `Some((end_offset, value))`. Once `capacity` entries
+/// are stored, each insert evicts the entry at the lowest offset: the one a
+/// forward-moving parse is least likely to ask for again.
+///
+/// Left-recursive seeds still growing are kept apart from the entries, where
+/// eviction cannot reach them.
+pub(crate) struct MemoCache<T> {
+    entries: BTreeMap<usize, Option<(usize, T)>>,
+    seeds: HashMap<usize, Option<(usize, T)>>,
+    capacity: usize,
+}
+
+impl<T: Clone> MemoCache<T> {
+    fn new(capacity: usize) -> Self {
+        MemoCache {
+            entries: BTreeMap::new(),
+            seeds: HashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Cached result at `offset`; `Some(None)` is a cached failure.
+    #[inline]
+    pub(crate) fn get(&self, offset: usize) -> Option<Option<(usize, T)>> {
+        if !self.seeds.is_empty()
+            && let Some(seed) = self.seeds.get(&offset)
+        {
+            return Some(seed.clone());
+        }
+        self.entries.get(&offset).cloned()
+    }
+
+    /// Set the seed growing at `offset`.
+    pub(crate) fn plant(&mut self, offset: usize, seed: Option<(usize, T)>) {
+        self.seeds.insert(offset, seed);
+    }
+
+    /// Stop growing the seed at `offset`, caching `result` in its place.
+    pub(crate) fn settle(&mut self, offset: usize, result: Option<(usize, T)>) {
+        self.uproot(offset);
+        self.insert(offset, result);
+    }
+
+    /// Drop the seed at `offset` without caching anything.
+    pub(crate) fn uproot(&mut self, offset: usize) {
+        self.seeds.remove(&offset);
+    }
+
+    /// Record the result of a parse that started at `offset`.
+    pub(crate) fn insert(&mut self, offset: usize, result: Option<(usize, T)>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&offset) {
+            self.entries.pop_first();
+        }
+        self.entries.insert(offset, result);
+    }
 }
 
+/// Type-erased `MemoCache`, so tables of any output type share one map.
+trait AnyMemoCache {}
 
+impl<T> AnyMemoCache for MemoCache<T> {}
+
+/// The packrat tables of one parse, one per memoized parser that has run.
+/// They live on the `ParserState`, so a parser shared between parses (or
+/// threads) keeps no results of its own. A cache, not parse state: clones
+/// start empty, and equality and hashing ignore it.
+#[derive(Default)]
+pub struct MemoTables<'a> {
+    tables: HashMap<MemoId, Box<dyn AnyMemoCache + 'a>>,
+}
+
+impl<'a> MemoTables<'a> {
+    /// The table of memoized parser `id`, created with `capacity` on first use.
+    #[inline]
+    pub(crate) fn table<T: Clone + 'a>(
+        &mut self,
+        id: MemoId,
+        capacity: usize,
+    ) -> &mut MemoCache<T> {
+        let table = self
+            .tables
+            .entry(id)
+            .or_insert_with(|| Box::new(MemoCache::<T>::new(capacity)));
+        let table: *mut dyn AnyMemoCache = &mut **table;
+        // SAFETY: `id` belongs to a single memoized parser, which has one
+        // output type, so every table stored under it is a `MemoCache<T>`.
+        unsafe { &mut *table.cast::<MemoCache<T>>() }
+    }
+
+    /// Number of memoized parsers with a table.
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Drop every cached result.
+    pub fn clear(&mut self) {
+        self.tables.clear();
+    }
+}
+
+impl Clone for MemoTables<'_> {
+    fn clone(&self) -> Self {
+        MemoTables::default()
+    }
+}
+
+impl std::fmt::Debug for MemoTables<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MemoTables({})", self.tables.len())
+    }
+}
+
+impl PartialEq for MemoTables<'_> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for MemoTables<'_> {}
+
+impl std::hash::Hash for MemoTables<'_> {
+    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
 
 #[derive(Pretty, Debug, Default, PartialEq, Clone, Hash, Eq)]
 pub struct ParserState<'a> {
@@ -86,6 +437,13 @@ pub struct ParserState<'a> {
 
     pub offset: usize,
     pub furthest_offset: usize,
+    /// Set when a `cut()` parser fails: the failure is then a hard error and
+    /// alternation no longer backtracks.
+    pub cut_offset: Option<usize>,
+    /// Latest user error; reported only if the parse stops within its span
+    /// (see `current_custom_error`).
+    #[pprint(skip)]
+    pub custom_error: Option<CustomError>,
 
     #[cfg(feature = "diagnostics")]
     #[pprint(skip)]
@@ -96,6 +454,49 @@ pub struct ParserState<'a> {
     #[cfg(feature = "diagnostics")]
     #[pprint(skip)]
     pub secondary_spans: SmallVec<[SecondarySpan; 4]>,
+
+    /// Results of `memoize()`d and `left_recursive` parsers in this parse.
+    #[pprint(skip)]
+    pub memo: MemoTables<'a>,
+
+    /// User-defined context; see `Parser::with_state` / `Parser::update_state`.
+    #[pprint(skip)]
+    pub context: Option<UserContext>,
+
+    /// Widths of the open `indented_block`s, innermost last.
+    #[pprint(skip)]
+    pub indent_stack: Vec<usize>,
+    /// How tabs and spaces count in indentation; see `Parser::indent_mode`.
+    #[pprint(skip)]
+    pub indent_mode: IndentMode,
+    /// Indentation of the line the innermost `line_fold` started on.
+    #[pprint(skip)]
+    pub fold_indent: Option<usize>,
+
+    /// Line index of `src`, built on the first line/column lookup.
+    #[pprint(skip)]
+    pub line_index: LazyLineIndex,
+
+    /// Recursive parsers (`recursive`, `lazy`, `left_recursive`) entered and
+    /// not yet left.
+    pub depth: usize,
+    /// Entering a recursive parser at this depth fails; see
+    /// `Parser::max_depth`.
+    #[pprint(skip)]
+    pub max_depth: Option<usize>,
+    /// Steps left, if budgeted: entering a recursive parser takes one. See
+    /// `Parser::fuel`.
+    #[pprint(skip)]
+    pub fuel: Option<u64>,
+    /// Set when `max_depth` or `fuel` stopped the parse; cleared with the
+    /// cut (see `clear_failure`).
+    #[pprint(skip)]
+    pub limit_error: Option<LimitError>,
+
+    /// Whitespace and comments skipped between tokens, if not ASCII
+    /// whitespace; see `Parser::trivia`.
+    #[pprint(skip)]
+    pub trivia: Option<Arc<Trivia>>,
 }
 
 impl<'a> ParserState<'a> {
@@ -108,24 +509,138 @@ impl<'a> ParserState<'a> {
         }
     }
 
+    /// A state carrying a user-defined context.
+    pub fn with_context<T: Any + Clone>(src: &'a str, context: T) -> ParserState<'a> {
+        ParserState {
+            context: Some(UserContext::new(context)),
+            ..ParserState::new(src)
+        }
+    }
+
+    /// The user context, if one of type `T` is set.
+    pub fn context<T: Any>(&self) -> Option<&T> {
+        self.context.as_ref()?.downcast_ref()
+    }
+
+    pub fn context_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.context.as_mut()?.downcast_mut()
+    }
+
+    pub fn set_context<T: Any + Clone>(&mut self, context: T) {
+        self.context = Some(UserContext::new(context));
+    }
+
+    /// Remove and return the user context if it has type `T`.
+    pub fn take_context<T: Any + Clone>(&mut self) -> Option<T> {
+        match self.context.take()?.downcast() {
+            Ok(value) => Some(value),
+            Err(context) => {
+                self.context = Some(context);
+                None
+            }
+        }
+    }
+
+    /// Put back the user context taken as `state.context.clone()` before a
+    /// branch that failed. Free when the branch left it alone; without a
+    /// context, neither the clone nor the restore does any work.
+    #[inline(always)]
+    pub fn restore_context(&mut self, checkpoint: &Option<UserContext>) {
+        let Some(checkpoint) = checkpoint else {
+            return;
+        };
+        if !UserContext::same(&self.context, checkpoint) {
+            self.context = Some(checkpoint.clone());
+        }
+    }
+
     pub fn is_at_end(&self) -> bool {
         self.offset >= self.end
     }
 
-    pub fn get_column_number(&self) -> usize {
-        let offset = self.offset;
-        match self.src[..offset].rfind('\n') {
-            Some(nl) => offset - nl - 1,
-            None => offset,
+    /// Record a user error for the input from `start` to the current offset.
+    /// The caller still fails by returning `None`.
+    #[cold]
+    pub fn record_error(
+        &mut self,
+        start: usize,
+        error: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) {
+        self.custom_error = Some(CustomError::new(start, self.offset, error));
+    }
+
+    /// The recorded user error, if the parse stopped within its span. Once
+    /// the parse has moved past it, the error belongs to an abandoned branch.
+    pub fn current_custom_error(&self) -> Option<CustomError> {
+        self.custom_error.clone().filter(|e| {
+            (self.offset == e.start || self.offset < e.end) && self.furthest_offset <= e.end
+        })
+    }
+
+    /// Enter a recursive parser: one level deeper, one step of fuel. Past
+    /// either limit, fails hard (as a `cut()` would) with a `LimitError`.
+    /// Pair a successful call with `leave`.
+    #[inline(always)]
+    pub fn enter(&mut self) -> Option<()> {
+        if self.max_depth.is_some_and(|max_depth| self.depth >= max_depth) {
+            return self.limit_exceeded(LimitError::Depth);
+        }
+        if let Some(fuel) = &mut self.fuel {
+            if *fuel == 0 {
+                return self.limit_exceeded(LimitError::Fuel);
+            }
+            *fuel -= 1;
+        }
+        self.depth += 1;
+        Some(())
+    }
+
+    #[inline(always)]
+    pub fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    #[cold]
+    fn limit_exceeded<T>(&mut self, error: LimitError) -> Option<T> {
+        self.limit_error = Some(error);
+        if self.cut_offset.is_none() {
+            self.cut_offset = Some(self.offset);
         }
+        None
+    }
+
+    /// Whether a committed failure is propagating (see `Parser::cut`).
+    #[inline(always)]
+    pub fn is_cut(&self) -> bool {
+        self.cut_offset.is_some()
+    }
+
+    /// Forget the cut and limit error of an earlier failure: when a parser
+    /// succeeds past it, and when `max_depth` or `fuel` starts a parse.
+    /// Callers check that one is pending first, keeping this off the hot path.
+    #[cold]
+    #[inline(never)]
+    pub fn clear_failure(&mut self) {
+        self.cut_offset = None;
+        self.limit_error = None;
+    }
+
+    /// Line index of `src`, built on first use and kept for the state.
+    pub fn line_index(&self) -> &LineIndex {
+        self.line_index.get_or_init(self.src)
+    }
+
+    /// Position of the current offset.
+    pub fn line_col(&self) -> LineCol {
+        self.line_index().line_col(self.offset)
+    }
+
+    pub fn get_column_number(&self) -> usize {
+        self.line_col().col
     }
 
     pub fn get_line_number(&self) -> usize {
-        self.src.as_bytes()[..self.offset]
-            .iter()
-            .filter(|&&c| c == b'\n')
-            .count()
-            + 1
+        self.line_col().line
     }
 
     /// Record an expected label at the current offset. No-op without `diagnostics` feature.
@@ -154,6 +669,46 @@ impl<'a> ParserState<'a> {
         }
     }
 
+    /// Replace the labels a failed parser recorded at `start` with `label`,
+    /// unless it got past `start`. `prev_furthest` and `prev_expected` are
+    /// `furthest_offset` and `expected.len()` from before the call, so labels
+    /// of sibling alternatives at `start` are kept. Used by `label()`.
+    #[cfg(feature = "diagnostics")]
+    pub fn relabel_expected(
+        &mut self,
+        start: usize,
+        prev_furthest: usize,
+        prev_expected: usize,
+        label: &'static str,
+    ) {
+        if self.offset > start || self.furthest_offset > start {
+            return;
+        }
+        if start > prev_furthest {
+            self.expected.clear();
+            self.suggestions.clear();
+            self.secondary_spans.clear();
+        } else {
+            self.expected.truncate(prev_expected);
+        }
+        if !self.expected.contains(&label) {
+            self.expected.push(label);
+        }
+        self.furthest_offset = start;
+    }
+
+    /// Fail at the current offset, expecting `label`. Without `diagnostics`,
+    /// just `None`.
+    #[cold]
+    pub(crate) fn fail<T>(&mut self, _label: &'static str) -> Option<T> {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.add_expected(_label);
+            self.furthest_offset = self.furthest_offset.max(self.offset);
+        }
+        None
+    }
+
     /// Record a structured suggestion. No-op without `diagnostics` feature.
     #[cfg(feature = "diagnostics")]
     #[inline(always)]
@@ -186,16 +741,7 @@ impl<'a> ParserState<'a> {
     #[cfg(feature = "diagnostics")]
     pub fn snapshot_diagnostic(&mut self, error_offset: usize) -> Diagnostic {
         let furthest = self.furthest_offset.max(error_offset);
-        let src_before = &self.src[..furthest];
-        let last_nl = src_before.rfind('\n');
-        let line = match last_nl {
-            Some(pos) => src_before[..=pos].chars().filter(|&c| c == '\n').count() + 1,
-            None => 1,
-        };
-        let column = match last_nl {
-            Some(pos) => furthest - pos - 1,
-            None => furthest,
-        };
+        let LineCol { line, col: column, .. } = self.line_index().line_col(furthest);
         let found_end = (furthest + 20).min(self.src.len());
         let found = self.src[furthest..found_end].replace('\n', "\\n");
 
@@ -208,7 +754,11 @@ impl<'a> ParserState<'a> {
             suggestions: std::mem::take(&mut self.suggestions).into_vec(),
             secondary_spans: std::mem::take(&mut self.secondary_spans).into_vec(),
             found,
+            cut_offset: self.cut_offset,
+            custom_error: self.current_custom_error(),
+            limit_error: self.limit_error.take(),
         };
+        self.custom_error = None;
         self.expected.clear();
         diag
     }
@@ -0,0 +1,179 @@
+use std::simd::prelude::*;
+use std::sync::Arc;
+
+use crate::parse::Parser;
+use crate::state::ParserState;
+
+// ── Trivia ────────────────────────────────────────────────────
+//
+// What `trim_whitespace`, `sep_by_ws` and the JSON parsers skip between
+// tokens. Without a configuration that is ASCII space, tab, CR and LF (see
+// `trim_leading_whitespace`); a `Trivia` on the state replaces it with its
+// own byte set, optionally Unicode whitespace, and line and block comments.
+
+/// Whitespace and comments to skip between tokens; install one with
+/// `Parser::trivia` or `ParserState::trivia`.
+///
+/// ```
+/// use parse_that::*;
+///
+/// let trivia = Trivia::new(b" \t\r\n\x0C")
+///     .line_comment("#")
+///     .block_comment("/*", "*/");
+/// let words = regex("[a-z]+").trim_whitespace().many(..).trivia(trivia);
+/// assert_eq!(
+///     words.parse("one # two\n\x0C/* three */ four"),
+///     Some(vec!["one", "four"])
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Trivia {
+    /// The whitespace bytes, once each, for the SIMD scan.
+    bytes: Vec<u8>,
+    /// The same bytes as a bitset, for the scalar scan.
+    set: [u64; 2],
+    unicode: bool,
+    line_comments: Vec<String>,
+    block_comments: Vec<(String, String)>,
+}
+
+impl Default for Trivia {
+    fn default() -> Self {
+        Trivia::new(b" \t\r\n")
+    }
+}
+
+impl Trivia {
+    /// Skip the bytes in `whitespace`, which must be ASCII.
+    pub fn new(whitespace: &[u8]) -> Trivia {
+        let mut set = [0u64; 2];
+        for &b in whitespace {
+            assert!(
+                b.is_ascii(),
+                "Trivia: whitespace byte {b:#04x} is not ASCII"
+            );
+            set[(b >> 6) as usize] |= 1 << (b & 63);
+        }
+        Trivia {
+            bytes: (0..128)
+                .filter(|&b| set[(b >> 6) as usize] >> (b & 63) & 1 != 0)
+                .collect(),
+            set,
+            unicode: false,
+            line_comments: Vec::new(),
+            block_comments: Vec::new(),
+        }
+    }
+
+    /// Also skip non-ASCII whitespace (`char::is_whitespace`).
+    pub fn unicode_whitespace(mut self) -> Trivia {
+        self.unicode = true;
+        self
+    }
+
+    /// Skip comments from `start` up to, not including, the next `\n`.
+    pub fn line_comment(mut self, start: impl Into<String>) -> Trivia {
+        let start = start.into();
+        assert!(!start.is_empty(), "Trivia: empty line comment delimiter");
+        self.line_comments.push(start);
+        self
+    }
+
+    /// Skip comments from `open` through the next `close`. They do not nest,
+    /// and an unterminated one is not trivia: the scan stops before it.
+    pub fn block_comment(mut self, open: impl Into<String>, close: impl Into<String>) -> Trivia {
+        let (open, close) = (open.into(), close.into());
+        assert!(!open.is_empty(), "Trivia: empty block comment delimiter");
+        self.block_comments.push((open, close));
+        self
+    }
+
+    /// Length of the trivia at `offset` in `src`.
+    pub fn skip(&self, src: &str, offset: usize) -> usize {
+        let mut i = offset;
+        loop {
+            i = self.skip_bytes(src.as_bytes(), i);
+            let Some(rest) = src.get(i..) else {
+                break;
+            };
+            match self.comment_len(rest).or_else(|| self.unicode_len(rest)) {
+                Some(n) => i += n,
+                None => break,
+            }
+        }
+        i - offset
+    }
+
+    #[inline(always)]
+    fn contains(&self, b: u8) -> bool {
+        b < 128 && self.set[(b >> 6) as usize] >> (b & 63) & 1 != 0
+    }
+
+    /// Skip whitespace bytes from `i`; the scan of `trim_leading_whitespace`
+    /// over this byte set.
+    #[inline]
+    fn skip_bytes(&self, bytes: &[u8], mut i: usize) -> usize {
+        let end = bytes.len();
+        if i >= end || !self.contains(bytes[i]) {
+            return i;
+        }
+        i += 1;
+
+        while i + 16 <= end {
+            let chunk = u8x16::from_slice(&bytes[i..i + 16]);
+            let mask = self.bytes.iter().fold(mask8x16::splat(false), |mask, &b| {
+                mask | chunk.simd_eq(u8x16::splat(b))
+            });
+            if mask.all() {
+                i += 16;
+                continue;
+            }
+            return i + (!mask).to_bitmask().trailing_zeros() as usize;
+        }
+
+        while i < end && self.contains(bytes[i]) {
+            i += 1;
+        }
+        i
+    }
+
+    fn comment_len(&self, rest: &str) -> Option<usize> {
+        for start in &self.line_comments {
+            if rest.starts_with(start.as_str()) {
+                let body = &rest.as_bytes()[start.len()..];
+                return Some(start.len() + memchr::memchr(b'\n', body).unwrap_or(body.len()));
+            }
+        }
+        for (open, close) in &self.block_comments {
+            if let Some(body) = rest.strip_prefix(open.as_str())
+                && let Some(n) = memchr::memmem::find(body.as_bytes(), close.as_bytes())
+            {
+                return Some(open.len() + n + close.len());
+            }
+        }
+        None
+    }
+
+    fn unicode_len(&self, rest: &str) -> Option<usize> {
+        if !self.unicode || rest.as_bytes().first()?.is_ascii() {
+            return None;
+        }
+        let c = rest.chars().next()?;
+        c.is_whitespace().then(|| c.len_utf8())
+    }
+}
+
+impl<'a, Output: 'a> Parser<'a, Output> {
+    /// Skip `trivia` instead of ASCII whitespace inside this parser: in
+    /// `trim_whitespace`, `sep_by_ws` and the JSON parsers.
+    pub fn trivia(self, trivia: Trivia) -> Parser<'a, Output> {
+        let trivia = Arc::new(trivia);
+        let with_trivia = move |state: &mut ParserState<'a>| {
+            let outer = state.trivia.replace(trivia.clone());
+            let result = self.call(state);
+            state.trivia = outer;
+            result
+        };
+        Parser::new(with_trivia)
+    }
+}
@@ -2,6 +2,7 @@
 mod tests {
     use parse_that::state::{ParserState, Span};
     use parse_that::*;
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
 
     // ── string / string_span ──────────────────────────────────
 
@@ -61,6 +62,19 @@ mod tests {
         assert_eq!(regex(r"\d+").parse(""), None);
     }
 
+    #[test]
+    fn test_regex_is_anchored() {
+        // A later match does not count, for regex leaves and span parsers alike
+        assert_eq!(regex(r"\d+").parse("abc123"), None);
+        assert_eq!(sp_regex(r"\d+").into_parser().parse("abc123"), None);
+        let mut state = ParserState::new("ab12");
+        state.offset = 2;
+        assert_eq!(regex(r"\d+").call(&mut state), Some("12"));
+        // Leftmost-first alternation and `^` behave as before
+        assert_eq!(regex("a|ab").parse("abc"), Some("a"));
+        assert_eq!(regex(r"^\w+").parse("word rest"), Some("word"));
+    }
+
     // ── take_while_span ───────────────────────────────────────
 
     #[test]
@@ -738,11 +752,11 @@ mod tests {
 
     fn gen_json_number(seed: &mut u64) -> String {
         let mut out = String::new();
-        if next_rand(seed) % 2 == 0 {
+        if next_rand(seed).is_multiple_of(2) {
             out.push('-');
         }
 
-        if next_rand(seed) % 10 == 0 {
+        if next_rand(seed).is_multiple_of(10) {
             out.push('0');
         } else {
             out.push((b'1' + (next_rand(seed) % 9) as u8) as char);
@@ -752,7 +766,7 @@ mod tests {
             }
         }
 
-        if next_rand(seed) % 3 == 0 {
+        if next_rand(seed).is_multiple_of(3) {
             out.push('.');
             let frac_len = (next_rand(seed) % 6 + 1) as usize;
             for _ in 0..frac_len {
@@ -760,8 +774,8 @@ mod tests {
             }
         }
 
-        if next_rand(seed) % 3 == 0 {
-            out.push(if next_rand(seed) % 2 == 0 { 'e' } else { 'E' });
+        if next_rand(seed).is_multiple_of(3) {
+            out.push(if next_rand(seed).is_multiple_of(2) { 'e' } else { 'E' });
             match next_rand(seed) % 3 {
                 0 => out.push('+'),
                 1 => out.push('-'),
@@ -881,4 +895,323 @@ mod tests {
         // at offset 0 but the memoized version caches the first attempt.
         assert_eq!(p.parse("hello?"), Some("hello"));
     }
+
+    /// A memoized `[a-z]+` that counts how often the inner parser runs.
+    fn counted_word<'a>(calls: &'a AtomicUsize) -> Parser<'a, &'a str> {
+        let word = regex(r"[a-z]+");
+        Parser::new(move |state: &mut ParserState<'a>| {
+            calls.fetch_add(1, Relaxed);
+            word.call(state)
+        })
+    }
+
+    #[test]
+    fn memoize_scoped_to_state() {
+        let calls = AtomicUsize::new(0);
+        let p = counted_word(&calls).memoize();
+        let twice = |src| {
+            let mut state = ParserState::new(src);
+            let first = p.call(&mut state);
+            state.offset = 0;
+            (first, p.call(&mut state), state.offset)
+        };
+        assert_eq!(twice("abc"), (Some("abc"), Some("abc"), 3));
+        assert_eq!(calls.load(Relaxed), 1);
+        // Same parser, new input: no stale hit from the previous document
+        assert_eq!(twice("xy"), (Some("xy"), Some("xy"), 2));
+        assert_eq!(calls.load(Relaxed), 2);
+        assert_eq!(twice("12"), (None, None, 0));
+        assert_eq!(calls.load(Relaxed), 3);
+    }
+
+    #[test]
+    fn memoize_capacity_bound() {
+        let calls = AtomicUsize::new(0);
+        let p = counted_word(&calls).memoize_with_capacity(0);
+        let mut state = ParserState::new("abc");
+        p.call(&mut state);
+        state.offset = 0;
+        p.call(&mut state);
+        assert_eq!(calls.load(Relaxed), 2);
+
+        let calls = AtomicUsize::new(0);
+        let p = counted_word(&calls)
+            .memoize_with_capacity(2)
+            .sep_by(string(","), 1..);
+        assert_eq!(p.parse("a,b,c,d"), Some(vec!["a", "b", "c", "d"]));
+        assert_eq!(calls.load(Relaxed), 4);
+    }
+
+    #[test]
+    fn memoize_span_reuses_and_rescopes() {
+        let word = sp_regex(r"[a-z]+").trim_whitespace().memoize_span();
+        let p = word.into_parser();
+        let mut state = ParserState::new("  abc  !");
+        let first = p.call(&mut state).unwrap();
+        assert_eq!((first.as_str(), state.offset), ("abc", 7));
+        state.offset = 0;
+        assert_eq!(p.call(&mut state), Some(first));
+        assert_eq!(state.offset, 7);
+        assert_eq!(p.parse(" de").map(|s| s.as_str()), Some("de"));
+        assert_eq!(p.parse("!"), None);
+    }
+
+    #[test]
+    fn memoize_interleaved_states_keep_their_tables() {
+        let calls = AtomicUsize::new(0);
+        let p = counted_word(&calls).memoize();
+        let (mut a, mut b) = (ParserState::new("abc"), ParserState::new("xy"));
+        for _ in 0..3 {
+            a.offset = 0;
+            b.offset = 0;
+            assert_eq!(p.call(&mut a), Some("abc"));
+            assert_eq!(p.call(&mut b), Some("xy"));
+        }
+        assert_eq!(calls.load(Relaxed), 2);
+        assert_eq!(a.memo.len(), 1);
+    }
+
+    #[test]
+    fn memoize_evicts_lowest_offset_first() {
+        let calls = AtomicUsize::new(0);
+        let p = counted_word(&calls).memoize_with_capacity(2);
+        let mut state = ParserState::new("a b c");
+        for offset in [0, 2, 4, 4, 2, 0] {
+            state.offset = offset;
+            p.call(&mut state);
+        }
+        // 0 was evicted to make room for 4; 2 and 4 were still cached
+        assert_eq!(calls.load(Relaxed), 4);
+    }
+
+    #[test]
+    fn memo_tables_do_not_affect_equality() {
+        let p = regex("[a-z]+").memoize();
+        let (mut a, b) = (ParserState::new("abc"), ParserState::new("abc"));
+        assert_eq!(a, b);
+        p.call(&mut a);
+        a.offset = 0;
+        assert_eq!(a, b);
+        assert!(a.clone().memo.is_empty());
+    }
+
+    // ── closures: map / filter / filter_map / validate ────────
+
+    #[test]
+    fn map_accepts_capturing_closures() {
+        let scale = 10;
+        let names = std::sync::Mutex::new(Vec::new());
+        let number = regex(r"\d+").map(move |s| s.parse::<i32>().unwrap() * scale);
+        let name = regex("[a-z]+").map(|s| {
+            let mut names = names.lock().unwrap();
+            names.push(s);
+            names.len() - 1
+        });
+        assert_eq!(number.parse("42"), Some(420));
+        assert_eq!(
+            name.sep_by(string(","), ..).parse("a,b,c"),
+            Some(vec![0, 1, 2])
+        );
+
+        let base = 100;
+        let at = regex("[a-z]+")
+            .map_with_state(move |s, offset, state| (s, base + offset, state.offset));
+        assert_eq!(string(" ").next(at).parse(" ab"), Some(("ab", 101, 3)));
+    }
+
+    #[test]
+    fn filter_and_filter_map() {
+        let limit = 3;
+        let short = regex("[a-z]+").filter(move |w| w.len() <= limit);
+        assert_eq!(short.parse("abc"), Some("abc"));
+        let mut state = ParserState::new("abcd");
+        assert_eq!(short.call(&mut state), None);
+        assert_eq!(state.offset, 0);
+
+        let keyword = regex("[a-z]+").filter_map(|w| match w {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        });
+        let value = keyword | regex("[a-z]+").map(|_| false);
+        assert_eq!(value.parse("true"), Some(true));
+        assert_eq!(value.parse("maybe"), Some(false));
+    }
+
+    #[test]
+    fn validate_records_reason() {
+        let even = regex(r"\d+")
+            .map(|s| s.parse::<u32>().unwrap())
+            .validate(|n| {
+                if n % 2 == 0 {
+                    Ok(())
+                } else {
+                    Err("odd number")
+                }
+            });
+        assert_eq!(even.parse("8"), Some(8));
+        let err = even.parse_or_error("7").unwrap_err();
+        assert_eq!(err.custom_error.unwrap().to_string(), "odd number");
+    }
+
+    // ── try_map ───────────────────────────────────────────────
+
+    fn byte<'a>() -> Parser<'a, u8> {
+        regex(r"\d+").try_map(|s| s.parse::<u8>())
+    }
+
+    #[test]
+    fn try_map_reports_custom_error() {
+        assert_eq!(byte().parse("255"), Some(255));
+
+        let err = byte().parse_or_error("256").unwrap_err();
+        let custom = err.custom_error.clone().unwrap();
+        assert_eq!((custom.start, custom.end), (0, 3));
+        assert!(custom.downcast_ref::<std::num::ParseIntError>().is_some());
+        assert_eq!(err.offset, 0);
+        assert!(err.to_string().ends_with(&custom.to_string()), "{err}");
+    }
+
+    #[test]
+    fn try_map_string_errors_and_spans() {
+        let unit = regex("[a-z]+").try_map(|u| match u {
+            "px" | "em" => Ok(u),
+            _ => Err(format!("unknown unit `{u}`")),
+        });
+        let length = regex(r"\d+").then(unit);
+        assert_eq!(length.parse("12px"), Some(("12", "px")));
+
+        let err = length.parse_or_error("12pt").unwrap_err();
+        let custom = err.custom_error.unwrap();
+        assert_eq!(custom.to_string(), "unknown unit `pt`");
+        assert_eq!((custom.start, custom.end), (2, 4));
+    }
+
+    #[test]
+    fn try_map_error_scoped_to_its_span() {
+        // The failed branch is abandoned for the fallback; the later failure
+        // at `?` is not blamed on it.
+        let p = byte().or(regex(r"\d+").map(|_| 0)).skip(string("!"));
+        assert_eq!(p.parse("300!"), Some(0));
+        let err = p.parse_or_error("300?").unwrap_err();
+        assert_eq!(err.offset, 3);
+        assert!(err.custom_error.is_none());
+
+        // A repetition stopping short of a rejected element still blames it
+        let err = byte()
+            .sep_by(string(","), 1..)
+            .eof()
+            .parse_or_error("1,2,300")
+            .unwrap_err();
+        let custom = err.custom_error.unwrap();
+        assert_eq!((err.offset, custom.start, custom.end), (3, 4, 7));
+    }
+
+    // ── cut / commit ──────────────────────────────────────────
+
+    /// `[n, n, ...]`, committed after the opening bracket, or any other word.
+    fn list_or_word<'a>(committed: bool) -> Parser<'a, Vec<&'a str>> {
+        let items = regex(r"\d+")
+            .sep_by(string(",").trim_whitespace(), ..)
+            .skip(string("]"));
+        let list = if committed {
+            string("[").commit(items).map(|(_, items)| items)
+        } else {
+            string("[").next(items)
+        };
+        list | regex(r"\S+").map(|w| vec![w])
+    }
+
+    #[test]
+    fn cut_stops_alternation() {
+        assert_eq!(list_or_word(true).parse("[1, 2]"), Some(vec!["1", "2"]));
+        assert_eq!(list_or_word(true).parse("word"), Some(vec!["word"]));
+        // Without the cut the broken list falls through to the word branch
+        assert_eq!(list_or_word(false).parse("[1, 2 x]"), Some(vec!["[1,"]));
+
+        let err = list_or_word(true).parse_or_error("[1, 2 x]").unwrap_err();
+        assert_eq!(err.cut_offset, Some(5));
+        assert_eq!((err.offset, err.line, err.column), (5, 1, 5));
+
+        let err = list_or_word(true)
+            .parse_or_error("[1,\n 2,\n x]")
+            .unwrap_err();
+        // The trailing `,` is backtracked, so `]` is missing right after `2`
+        assert_eq!(err.cut_offset, Some(6));
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let err = list_or_word(false).parse_or_error("").unwrap_err();
+        assert_eq!(err.cut_offset, None);
+    }
+
+    #[test]
+    fn cut_propagates_through_repetition() {
+        let pair = || string("a").commit(string("b"));
+        assert_eq!(pair().many(..).parse("ababc").map(|v| v.len()), Some(2));
+        assert_eq!(pair().many(..).parse("ababac"), None);
+        assert_eq!(pair().opt().parse("ac"), None);
+        assert_eq!(pair().sep_by(string(","), ..).parse("ab,ac"), None);
+        assert_eq!(
+            one_of(vec![pair(), string("a").then(string("c"))]).parse("ac"),
+            None
+        );
+        assert_eq!(
+            alt!(pair(), string("a").then(string("c"))).parse("ac"),
+            None
+        );
+        assert_eq!(pair().negate().parse("ac"), None);
+
+        let (result, state) = pair().many(..).parse_return_state("abac");
+        assert_eq!((result, state.cut_offset), (None, Some(3)));
+    }
+
+    /// Runs `inner` and rewinds on failure, succeeding either way.
+    fn absorb<'a>(inner: Parser<'a, (&'a str, &'a str)>) -> Parser<'a, ()> {
+        Parser::new(move |state: &mut ParserState<'a>| {
+            let start = state.offset;
+            if inner.call(state).is_none() {
+                state.offset = start;
+            }
+            Some(())
+        })
+    }
+
+    #[test]
+    fn cut_ends_when_its_failure_is_swallowed() {
+        let pair = || string("a").commit(string("b"));
+        let p = absorb(pair()).then(string("x").or(string("a")));
+        assert_eq!(p.parse("ay"), Some(((), "a")));
+        let p = absorb(pair()).then(alt!(string("x"), string("a")));
+        assert_eq!(p.parse("ay"), Some(((), "a")));
+        let p = absorb(pair()).then(string("z")).map(|(_, z)| z) | string("a");
+        assert_eq!(p.parse("ay"), Some("a"));
+        let err = absorb(pair()).then(string("z")).parse_or_error("ay").unwrap_err();
+        assert_eq!((err.cut_offset, err.offset), (None, 0));
+    }
+
+    #[test]
+    fn cut_span_stops_one_of() {
+        let list = sp_string("[").then_span(sp_regex(r"\d+").then_span(sp_string("]")).cut_span());
+        let p = (list | sp_regex(r"\S+")).into_parser();
+        assert_eq!(p.parse("[12]").map(|s| s.as_str()), Some("[12]"));
+        assert_eq!(p.parse("x[").map(|s| s.as_str()), Some("x["));
+        assert_eq!(p.parse("[12"), None);
+        assert_eq!(p.parse_or_error("[12").unwrap_err().cut_offset, Some(3));
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn recover_catches_cut() {
+        clear_collected_diagnostics();
+        let item = string("(")
+            .commit(regex("[a-z]+").skip(string(")")))
+            .map(|(_, w)| w)
+            .recover(regex(r"[^)]*\)").map(|_| ()), "?");
+        let p = item.many(..).eof();
+        assert_eq!(p.parse("(a)(1)(b)"), Some(vec!["a", "?", "b"]));
+        let diags = get_collected_diagnostics();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].cut_offset, Some(4));
+        clear_collected_diagnostics();
+    }
 }
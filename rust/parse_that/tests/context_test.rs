@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::state::ParserState;
+    use parse_that::*;
+    use std::collections::HashSet;
+
+    type Types = HashSet<String>;
+
+    #[derive(Debug, PartialEq)]
+    enum Stmt<'a> {
+        Typedef(&'a str),
+        Decl(&'a str, &'a str),
+        Call(&'a str, &'a str),
+    }
+
+    fn ident<'a>() -> Parser<'a, &'a str> {
+        regex("[a-z]+").trim_whitespace()
+    }
+
+    /// `a(b);` is a declaration when `a` is a typedef name, else a call.
+    fn statement<'a>() -> Parser<'a, Stmt<'a>> {
+        let typedef = string("typedef")
+            .next(ident())
+            .update_state(|name: &&str, types: &mut Types| {
+                types.insert(name.to_string());
+            })
+            .map(Stmt::Typedef);
+        let type_name = ident()
+            .with_state(|name, types: &Types| types.contains(name).then_some(name))
+            .filter_map(|name| name);
+        let decl = type_name
+            .then(ident().wrap(string("("), string(")")))
+            .map(|(ty, name)| Stmt::Decl(ty, name));
+        let call = ident()
+            .then(ident().wrap(string("("), string(")")))
+            .map(|(f, arg)| Stmt::Call(f, arg));
+        (typedef | decl | call).skip(string(";").trim_whitespace())
+    }
+
+    #[test]
+    fn test_typedef_names_disambiguate() {
+        let src = "f(x); typedef t; t(y); g(t);";
+        let (stmts, types) = statement()
+            .many(..)
+            .eof()
+            .parse_with_context(src, Types::new());
+        assert_eq!(
+            stmts,
+            Some(vec![
+                Stmt::Call("f", "x"),
+                Stmt::Typedef("t"),
+                Stmt::Decl("t", "y"),
+                Stmt::Call("g", "t"),
+            ])
+        );
+        assert_eq!(types, Types::from(["t".to_string()]));
+    }
+
+    fn push_then_b<'a>() -> Parser<'a, ()> {
+        string("a")
+            .update_state(|_, stack: &mut Vec<u32>| stack.push(1))
+            .then(string("b"))
+            .map(|_| ())
+    }
+
+    #[test]
+    fn test_alternation_rolls_back_context() {
+        let (_, stack) =
+            (push_then_b() | string("a").map(|_| ())).parse_with_context("ac", vec![0u32]);
+        assert_eq!(stack, [0]);
+        let p = alt!(push_then_b(), push_then_b(), string("a").map(|_| ()));
+        let (_, stack) = p.parse_with_context("ac", vec![0u32]);
+        assert_eq!(stack, [0]);
+        let p = one_of(vec![push_then_b(), string("a").map(|_| ())]);
+        let (_, stack) = p.parse_with_context("ac", vec![0u32]);
+        assert_eq!(stack, [0]);
+        // A branch that succeeds keeps its update
+        let (_, stack) =
+            (push_then_b() | string("a").map(|_| ())).parse_with_context("ab", vec![0u32]);
+        assert_eq!(stack, [0, 1]);
+    }
+
+    #[test]
+    fn test_save_state_rolls_back_context() {
+        let (_, stack) = push_then_b()
+            .save_state()
+            .opt()
+            .parse_with_context("ac", vec![0u32]);
+        assert_eq!(stack, [0]);
+        let (_, stack) = push_then_b().opt().parse_with_context("ac", vec![0u32]);
+        assert_eq!(stack, [0, 1]);
+    }
+
+    /// Counts its clones in a shared cell.
+    struct Counted(std::rc::Rc<std::cell::Cell<usize>>, u32);
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            Counted(self.0.clone(), self.1)
+        }
+    }
+
+    #[test]
+    fn test_alternation_copies_context_only_on_update() {
+        let clones = std::rc::Rc::new(std::cell::Cell::new(0));
+        let word = || string("x") | string("a");
+        let mut state = ParserState::with_context("aaaa", Counted(clones.clone(), 0));
+        assert_eq!(word().many(..).call(&mut state).map(|v| v.len()), Some(4));
+        assert_eq!(clones.get(), 0);
+
+        let count = string("a").update_state(|_, n: &mut Counted| n.1 += 1);
+        let word = count | string("x");
+        let mut state = ParserState::with_context("aaaa", Counted(clones.clone(), 0));
+        assert_eq!(word.many(..).call(&mut state).map(|v| v.len()), Some(4));
+        assert_eq!(state.context::<Counted>().map(|n| n.1), Some(4));
+        assert_eq!(clones.get(), 4);
+    }
+
+    /// Raw text up to the terminator stored in the context.
+    fn heredoc_body<'a>() -> Parser<'a, &'a str> {
+        Parser::new(|state: &mut ParserState<'a>| {
+            let term = format!("\n{}", state.context::<String>()?);
+            let start = state.offset;
+            let len = state.src[start..].find(&term)?;
+            state.offset += len + term.len();
+            Some(&state.src[start..start + len])
+        })
+    }
+
+    #[test]
+    fn test_heredoc_terminator() {
+        let open = string("<<")
+            .next(regex("[A-Z]+"))
+            .skip(string("\n"))
+            .update_state(|tag: &&str, term: &mut String| *term = tag.to_string());
+        let heredoc = open.next(heredoc_body());
+        let src = "<<EOT\nline one\nEND\nline two\nEOT";
+        let (text, term) = heredoc.parse_with_context(src, String::new());
+        assert_eq!(text, Some("line one\nEND\nline two"));
+        assert_eq!(term, "EOT");
+    }
+
+    #[test]
+    fn test_state_context_accessors() {
+        let mut state = ParserState::with_context("", 1u8);
+        assert_eq!(state.context::<u8>(), Some(&1));
+        assert_eq!(state.context::<u16>(), None);
+        *state.context_mut::<u8>().unwrap() += 1;
+        assert_eq!(state.take_context::<u16>(), None);
+        assert_eq!(state.take_context::<u8>(), Some(2));
+        assert!(state.context.is_none());
+        state.set_context(vec!["x"]);
+        assert_eq!(
+            format!("{:?}", state.context.unwrap()),
+            "UserContext<alloc::vec::Vec<&str>>"
+        );
+    }
+
+    #[test]
+    fn test_missing_context_fails() {
+        let read = string("a").with_state(|a, n: &u8| (a, *n));
+        let err = read.parse_or_error("a").unwrap_err();
+        let custom = err.custom_error.unwrap();
+        assert_eq!((custom.start, custom.end), (0, 1));
+        assert_eq!(custom.to_string(), "no parser context of type `u8`");
+        assert_eq!(read.parse_with_context("a", 7u8).0, Some(("a", 7)));
+
+        let wrong_type = string("a").update_state(|_, n: &mut u8| *n += 1);
+        let (result, context) = wrong_type.parse_with_context("a", 'x');
+        assert_eq!((result, context), (None, 'x'));
+    }
+}
@@ -249,7 +249,7 @@ mod tests {
 
         let has_success = valid_rules
             .iter()
-            .any(|(selectors, _)| selectors.iter().any(|s| *s == "success"));
+            .any(|(selectors, _)| selectors.contains(&"success"));
         assert!(has_success, "should have parsed the .success rule");
     }
 
@@ -1,7 +1,7 @@
 #[cfg(feature = "diagnostics")]
 mod tests {
     use parse_that::*;
-    use smallvec::{SmallVec, smallvec};
+    use smallvec::smallvec;
 
     /// Strip ANSI escape codes for comparison.
     fn strip_ansi(s: &str) -> String {
@@ -317,6 +317,104 @@ mod tests {
         );
     }
 
+    // ── label() ────────────────────────────────────────────────
+
+    fn labeled_value<'a>() -> Parser<'a, &'a str> {
+        (string("{") | string("[") | regex(r"-?\d+")).label("value")
+    }
+
+    #[test]
+    fn test_label_replaces_expected_set() {
+        let (result, state) = labeled_value().parse_return_state("?");
+        assert!(result.is_none());
+        assert_eq!(state.expected.as_slice(), ["value"]);
+
+        let err = string("let ")
+            .then(labeled_value())
+            .parse_or_error("let ?")
+            .unwrap_err();
+        assert_eq!(err.expected, ["value"]);
+        assert_eq!(err.furthest_offset, 4);
+    }
+
+    #[test]
+    fn test_label_keeps_siblings_and_inner_progress() {
+        let p = string("true").label("boolean") | regex(r"\d+").label("number");
+        let (_, state) = p.parse_return_state("?");
+        assert_eq!(state.expected.as_slice(), ["boolean", "number"]);
+
+        // Failing past its start, the inner parser's own labels are kept
+        let list = string("[").then(string("]")).label("list");
+        let (_, state) = list.parse_return_state("[?");
+        assert_eq!(state.expected.as_slice(), ["\"]\""]);
+    }
+
+    #[test]
+    fn test_label_in_format_diagnostic() {
+        let mut state = ParserState::new("let ?");
+        let p = string("let ").then(labeled_value());
+        assert!(p.call(&mut state).is_none());
+        let diag = state.snapshot_diagnostic(0);
+        let out = strip_ansi(&format_diagnostic(&diag, "let ?"));
+        assert!(out.contains("value"), "{out}");
+        assert!(!out.contains("\"{\""), "{out}");
+    }
+
+    #[test]
+    fn test_span_parser_label() {
+        let p = (sp_string("{") | sp_regex(r"\d+")).label("value");
+        let mut state = ParserState::new("?");
+        assert!(p.call(&mut state).is_none());
+        assert_eq!(state.expected.as_slice(), ["value"]);
+
+        let p = sp_string("[")
+            .then_span(sp_string("]").label("closing bracket"))
+            .label("list");
+        let mut state = ParserState::new("[?");
+        assert!(p.call(&mut state).is_none());
+        assert_eq!(state.expected.as_slice(), ["closing bracket"]);
+    }
+
+    #[test]
+    fn test_custom_error_in_diagnostic() {
+        let src = "port 99999";
+        let port = string("port ").next(regex(r"\d+").try_map(|s| s.parse::<u16>()));
+        let mut state = ParserState::new(src);
+        assert!(port.call(&mut state).is_none());
+        let diag = state.snapshot_diagnostic(0);
+        let custom = diag.custom_error.clone().expect("custom error");
+        assert_eq!((custom.start, custom.end), (5, 10));
+        let out = strip_ansi(&format_diagnostic(&diag, src));
+        assert!(out.contains("too large"), "{out}");
+        assert!(state.custom_error.is_none());
+    }
+
+    #[test]
+    fn test_indent_error_in_diagnostic() {
+        let src = "a:\n    b\n  c\n";
+        let name = || regex("[a-z]+");
+        let p = same_indent(name().skip(string(":")).then(indented_block(name()))).many(..);
+        let mut state = ParserState::new(src);
+        assert!(p.call(&mut state).is_none());
+        let diag = state.snapshot_diagnostic(state.cut_offset.unwrap());
+        assert_eq!((diag.line, diag.column), (3, 0));
+        let out = strip_ansi(&format_diagnostic(&diag, src));
+        assert!(out.contains("inconsistent indentation"), "{out}");
+    }
+
+    #[test]
+    fn test_limit_error_in_diagnostic() {
+        let src = "[\n  [[[1]]]\n]";
+        let p = parse_that::parsers::json::json_parser().max_depth(2);
+        let mut state = ParserState::new(src);
+        assert!(p.call(&mut state).is_none());
+        let diag = state.snapshot_diagnostic(state.cut_offset.unwrap());
+        assert_eq!(diag.limit_error, Some(LimitError::Depth));
+        assert_eq!((diag.line, diag.column), (2, 4));
+        let out = strip_ansi(&format_diagnostic(&diag, src));
+        assert!(out.contains("nesting depth limit exceeded"), "{out}");
+    }
+
     // =========================================================================
     // CSS-grammar-based diagnostic tests
     // =========================================================================
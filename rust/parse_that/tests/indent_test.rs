@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::state::ParserState;
+    use parse_that::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Stmt {
+        Line(String),
+        Block(String, Vec<Stmt>),
+    }
+
+    fn line(name: &str) -> Stmt {
+        Stmt::Line(name.to_string())
+    }
+
+    fn block(name: &str, body: Vec<Stmt>) -> Stmt {
+        Stmt::Block(name.to_string(), body)
+    }
+
+    /// `name` or `name:` followed by an indented block of statements.
+    fn stmt<'a>() -> Parser<'a, Stmt> {
+        lazy(|| {
+            let name = || regex("[a-z]+");
+            let header = name()
+                .skip(string(":"))
+                .then(indented_block(stmt()))
+                .map(|(name, body)| block(name, body));
+            header | name().map(line)
+        })
+    }
+
+    fn program<'a>() -> Parser<'a, Vec<Stmt>> {
+        same_indent(stmt()).many(..)
+    }
+
+    fn indent_error(err: &ParseError) -> IndentError {
+        let custom = err.custom_error.as_ref().expect("custom error");
+        *custom.downcast_ref::<IndentError>().expect("indent error")
+    }
+
+    #[test]
+    fn test_nested_blocks() {
+        let src = "a\nb:\n  c\n\n  d:\n      e\n  f\ng\n";
+        let expected = vec![
+            line("a"),
+            block("b", vec![line("c"), block("d", vec![line("e")]), line("f")]),
+            line("g"),
+        ];
+        let (result, state) = program().parse_return_state(src);
+        assert_eq!(result, Some(expected));
+        assert_eq!(state.offset, src.len() - 1);
+        assert!(state.indent_stack.is_empty());
+    }
+
+    #[test]
+    fn test_dedent_closes_several_blocks() {
+        let src = "a:\n  b:\n    c:\n      d\ne\n";
+        let expected = vec![
+            block("a", vec![block("b", vec![block("c", vec![line("d")])])]),
+            line("e"),
+        ];
+        assert_eq!(program().parse(src), Some(expected));
+    }
+
+    #[test]
+    fn test_block_needs_deeper_line() {
+        // `a:` has no body, so it is not a header; `:` then stops the program
+        let (result, state) = program().parse_return_state("a:\nb\n");
+        assert_eq!(result, Some(vec![line("a")]));
+        assert_eq!(state.offset, 1);
+        assert_eq!(indented_block(stmt()).parse(""), None);
+    }
+
+    #[test]
+    fn test_inconsistent_dedent() {
+        let src = "b:\n    c\n  d\n";
+        let err = program().parse_or_error(src).unwrap_err();
+        assert_eq!(indent_error(&err), IndentError::Inconsistent { found: 2 });
+        assert_eq!((err.line, err.column), (3, 0));
+        assert_eq!(err.cut_offset, Some(9));
+        let custom = err.custom_error.as_ref().unwrap();
+        assert_eq!((custom.start, custom.end), (9, 11));
+        assert!(
+            err.to_string().contains("inconsistent indentation"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_unexpected_indent() {
+        let err = program().parse_or_error("a\n  b\n").unwrap_err();
+        assert_eq!(
+            indent_error(&err),
+            IndentError::Unexpected {
+                expected: 0,
+                found: 2
+            }
+        );
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_indent_modes() {
+        let src = "b:\n\tc\n        d\n";
+        let expected = vec![block("b", vec![line("c"), line("d")])];
+        assert_eq!(program().parse(src), Some(expected));
+
+        let narrow = program().indent_mode(IndentMode::Mixed { tab_width: 4 });
+        let err = narrow.parse_or_error(src).unwrap_err();
+        assert_eq!(
+            indent_error(&err),
+            IndentError::Unexpected {
+                expected: 4,
+                found: 8
+            }
+        );
+
+        let spaces = program().indent_mode(IndentMode::Spaces);
+        let err = spaces.parse_or_error(src).unwrap_err();
+        assert_eq!(indent_error(&err), IndentError::Tab);
+        assert_eq!(err.line, 2);
+
+        let tabs = program().indent_mode(IndentMode::Tabs);
+        let src = "b:\n\tc\n\t\td\n";
+        let err = tabs.parse_or_error(src).unwrap_err();
+        assert_eq!(
+            indent_error(&err),
+            IndentError::Unexpected {
+                expected: 1,
+                found: 2
+            }
+        );
+        let err = tabs.parse_or_error("b:\n\tc\n  d\n").unwrap_err();
+        assert_eq!(indent_error(&err), IndentError::Space);
+
+        // The mode only applies inside the parser
+        let mut state = ParserState::new("a");
+        assert!(spaces.call(&mut state).is_some());
+        assert_eq!(state.indent_mode, IndentMode::default());
+    }
+
+    #[test]
+    fn test_line_fold_in_block() {
+        let words = || line_fold(regex("[a-z]+").sep_by(fold_ws(), 1..));
+        let entry = regex("[a-z]+")
+            .skip(string(":"))
+            .next(fold_ws())
+            .next(words());
+        let items = string("items:").next(indented_block(entry));
+        let src = "items:\n  x: one\n    two\n\n      three\n  y: four\nrest";
+        let (result, state) = items.parse_return_state(src);
+        assert_eq!(
+            result,
+            Some(vec![vec!["one", "two", "three"], vec!["four"]])
+        );
+        assert_eq!(&src[state.offset..], "\nrest");
+        assert_eq!(state.fold_indent, None);
+    }
+
+    #[test]
+    fn test_fold_ws_outside_fold() {
+        let words = regex("[a-z]+").sep_by(fold_ws(), 1..);
+        let (result, state) = words.parse_return_state("a b\n  c");
+        assert_eq!(result, Some(vec!["a", "b"]));
+        assert_eq!(state.offset, 3);
+    }
+}
@@ -0,0 +1,239 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::json::JsonValue;
+    use parse_that::parsers::json5::{
+        json5_document_parser, json5_parser, json5_value, unescape_json5_string,
+    };
+    use parse_that::span_parser::{sp_json5_identifier, sp_json5_number, sp_json5_ws_comment};
+    use std::borrow::Cow;
+    use std::fs;
+
+    fn num(n: f64) -> JsonValue<'static> {
+        JsonValue::Number(n)
+    }
+
+    fn string(s: &str) -> JsonValue<'_> {
+        JsonValue::String(Cow::Borrowed(s))
+    }
+
+    fn array(items: Vec<JsonValue<'_>>) -> JsonValue<'_> {
+        JsonValue::Array(Box::new(items))
+    }
+
+    fn object<'a>(entries: Vec<(&'a str, JsonValue<'a>)>) -> JsonValue<'a> {
+        JsonValue::Object(Box::new(
+            entries
+                .into_iter()
+                .map(|(k, v)| (Cow::Borrowed(k), v))
+                .collect(),
+        ))
+    }
+
+    // ── JSON compatibility ──────────────────────────────────────────
+
+    #[test]
+    fn test_json5_accepts_json_vectors() {
+        let content = fs::read_to_string("../../grammar/tests/json/valid.jsonl").unwrap();
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            let json = parse_that::parsers::json::json_parser().parse(line);
+            assert_eq!(json5_parser().parse(line), json, "mismatch on {line}");
+        }
+    }
+
+    // ── JSONC ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_jsonc_comments_and_trailing_commas() {
+        let src = r#"
+            // settings
+            {
+                "editor.tabSize": 4, /* spaces */
+                "files.exclude": ["target", "node_modules",],
+            }
+        "#;
+        assert_eq!(
+            json5_parser().parse(src),
+            Some(object(vec![
+                ("editor.tabSize", num(4.0)),
+                (
+                    "files.exclude",
+                    array(vec![string("target"), string("node_modules")])
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_comment_only_lines_and_eof_comment() {
+        assert_eq!(
+            json5_parser().parse("[1 // one\n, 2] // end"),
+            Some(array(vec![num(1.0), num(2.0)]))
+        );
+        assert_eq!(json5_parser().parse("/**/1/**/"), Some(num(1.0)));
+    }
+
+    // ── JSON5 ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_json5_spec_example() {
+        let src = r#"{
+  // comments
+  unquoted: 'and you can quote me on that',
+  singleQuotes: 'I can use "double quotes" here',
+  lineBreaks: "Look, Mom! \
+No \\n's!",
+  hexadecimal: 0xdecaf,
+  leadingDecimalPoint: .8675309, andTrailing: 8675309.,
+  positiveSign: +1,
+  trailingComma: 'in objects', andIn: ['arrays',],
+  "backwardsCompatible": "with JSON",
+}"#;
+        let value = json5_parser().parse(src).unwrap();
+        let JsonValue::Object(entries) = value else {
+            panic!("expected Object");
+        };
+        let get = |key: &str| {
+            entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.clone())
+                .unwrap()
+        };
+        assert_eq!(get("unquoted"), string("and you can quote me on that"));
+        assert_eq!(
+            get("singleQuotes"),
+            string("I can use \"double quotes\" here")
+        );
+        assert_eq!(get("lineBreaks"), string("Look, Mom! No \\n's!"));
+        assert_eq!(get("hexadecimal"), num(912559.0));
+        assert_eq!(get("leadingDecimalPoint"), num(0.8675309));
+        assert_eq!(get("andTrailing"), num(8675309.0));
+        assert_eq!(get("positiveSign"), num(1.0));
+        assert_eq!(get("andIn"), array(vec![string("arrays")]));
+        assert_eq!(get("backwardsCompatible"), string("with JSON"));
+        assert_eq!(entries.len(), 10);
+    }
+
+    #[test]
+    fn test_json5_numbers() {
+        let parser = json5_value();
+        assert_eq!(parser.parse("Infinity"), Some(num(f64::INFINITY)));
+        assert_eq!(parser.parse("-Infinity"), Some(num(f64::NEG_INFINITY)));
+        assert_eq!(parser.parse("-0XFF"), Some(num(-255.0)));
+        assert_eq!(parser.parse("1e3"), Some(num(1000.0)));
+        assert_eq!(parser.parse("+.5e-1"), Some(num(0.05)));
+        match parser.parse("NaN") {
+            Some(JsonValue::Number(n)) => assert!(n.is_nan()),
+            other => panic!("expected NaN, got {other:?}"),
+        }
+        for bad in ["0x", ".", "+", "1e", "007", "Inf"] {
+            assert_eq!(
+                sp_json5_number()
+                    .into_parser()
+                    .parse(bad)
+                    .map(|s| s.as_str()),
+                None,
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_json5_string_escapes() {
+        let parser = json5_value();
+        assert_eq!(parser.parse(r"'it\'s'"), Some(string("it's")));
+        assert_eq!(
+            parser.parse(r"'\x41é\v\0\a\r'"),
+            Some(string("A\u{e9}\u{0B}\0a\r"))
+        );
+        assert_eq!(parser.parse("'a\\\r\nb'"), Some(string("ab")));
+        assert_eq!(parser.parse(r#""😀""#), Some(string("\u{1F600}")));
+        assert_eq!(parser.parse("'plain'"), Some(string("plain")));
+        assert!(matches!(
+            parser.parse("'plain'"),
+            Some(JsonValue::String(Cow::Borrowed(_)))
+        ));
+
+        for bad in ["'a\nb'", r"'\1'", r"'\01'", r"'\xZZ'", r"'\ud800'", "'open"] {
+            assert_eq!(parser.parse(bad), None, "{bad:?}");
+        }
+        assert_eq!(unescape_json5_string(r"\u12"), None);
+    }
+
+    #[test]
+    fn test_json5_identifier_keys() {
+        assert_eq!(
+            json5_parser().parse("{ $id: 1, _x2: 2, café: 3 }"),
+            Some(object(vec![
+                ("$id", num(1.0)),
+                ("_x2", num(2.0)),
+                ("café", num(3.0))
+            ]))
+        );
+        assert_eq!(sp_json5_identifier().into_parser().parse("2abc"), None);
+        assert_eq!(
+            sp_json5_identifier()
+                .into_parser()
+                .parse("a-b")
+                .map(|s| s.as_str()),
+            Some("a")
+        );
+    }
+
+    #[test]
+    fn test_json5_rejects() {
+        for bad in [
+            "[1,,2]",
+            "[,]",
+            "{,}",
+            "{a b: 1}",
+            "{a: 1 b: 2}",
+            "{1: 2}",
+            "[1 /* unterminated",
+            "undefined",
+        ] {
+            assert_eq!(json5_parser().parse(bad), None, "{bad:?}");
+        }
+    }
+
+    // ── Comment spans ───────────────────────────────────────────────
+
+    #[test]
+    fn test_document_collects_comments() {
+        let src = "// head\n{ a: /* inline */ 1, // tail\n b: '// not a comment' }\n/* end */";
+        let doc = json5_document_parser().parse(src).unwrap();
+        let comments: Vec<&str> = doc.comments.iter().map(|s| s.as_str()).collect();
+        assert_eq!(
+            comments,
+            ["// head", "/* inline */", "// tail", "/* end */"]
+        );
+        assert_eq!(
+            doc.value,
+            object(vec![("a", num(1.0)), ("b", string("// not a comment"))])
+        );
+
+        // Each parse reports only its own comments, and leaves the user
+        // context alone.
+        let parser = json5_document_parser();
+        assert_eq!(parser.parse("1 // x").unwrap().comments.len(), 1);
+        assert_eq!(parser.parse("2").unwrap().comments.len(), 0);
+        let (doc, context) = parser.parse_with_context("['\"/*', \"'//\"] // y", 7u8);
+        let comments: Vec<&str> = doc.unwrap().comments.iter().map(|s| s.as_str()).collect();
+        assert_eq!(comments, ["// y"]);
+        assert_eq!(context, 7);
+    }
+
+    #[test]
+    fn test_ws_comment_scanner() {
+        let src = " \u{FEFF}\u{A0}// a\u{2028}/* b */\t1";
+        let span = sp_json5_ws_comment().into_parser().parse(src).unwrap();
+        assert_eq!(&src[span.end..], "1");
+        assert_eq!(
+            sp_json5_ws_comment()
+                .into_parser()
+                .parse("1")
+                .map(|s| s.end),
+            Some(0)
+        );
+    }
+}
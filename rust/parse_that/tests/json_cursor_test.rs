@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::json::{JsonValue, json_parser};
+    use parse_that::parsers::json_cursor::{JsonCursor, JsonKind};
+    use std::borrow::Cow;
+
+    /// A canada.json-shaped document: a FeatureCollection with long coordinate arrays.
+    fn feature_collection(features: usize) -> String {
+        let feature = |i: usize| {
+            let coords: Vec<String> = (0..200)
+                .map(|j| format!("[{}.{j}, -{i}.5e1]", i * 7 + j))
+                .collect();
+            format!(
+                r#"{{"type": "Feature", "properties": {{"name": "f{i} [\"quoted\"] {{x}}"}},
+                    "geometry": {{"type": "Polygon", "coordinates": [[{}]]}}}}"#,
+                coords.join(",")
+            )
+        };
+        let features: Vec<String> = (0..features).map(feature).collect();
+        format!(
+            "  {{\"type\": \"FeatureCollection\", \"features\": [\n{}\n]}}  ",
+            features.join(",\n")
+        )
+    }
+
+    #[test]
+    fn test_cursor_navigation() {
+        let src = feature_collection(8);
+        let geometry = JsonCursor::new(&src)
+            .get("features")
+            .and_then(|f| f.index(3))
+            .and_then(|f| f.get("geometry"))
+            .unwrap();
+        assert_eq!(geometry.kind(), Some(JsonKind::Object));
+        assert_eq!(
+            geometry.get("type").and_then(|t| t.as_str()),
+            Some(Cow::Borrowed("Polygon"))
+        );
+
+        let first_point = geometry
+            .get("coordinates")
+            .and_then(|c| c.index(0))
+            .and_then(|ring| ring.index(1))
+            .unwrap();
+        assert_eq!(first_point.index(0).and_then(|x| x.as_f64()), Some(22.1));
+        assert_eq!(first_point.index(1).and_then(|y| y.as_f64()), Some(-35.0));
+    }
+
+    #[test]
+    fn test_cursor_matches_full_parse() {
+        let src = feature_collection(4);
+        let full = json_parser().parse(&src).unwrap();
+        let cursor = JsonCursor::new(&src);
+
+        for i in 0..4 {
+            let ptr = format!("/features/{i}/properties/name");
+            let expected = full.pointer(&ptr).unwrap();
+            let found = cursor
+                .get("features")
+                .and_then(|f| f.index(i))
+                .and_then(|f| f.get("properties"))
+                .and_then(|p| p.get("name"))
+                .unwrap();
+            assert_eq!(found.value().as_ref(), Some(expected));
+        }
+
+        let geometry = cursor
+            .get("features")
+            .and_then(|f| f.index(2))
+            .and_then(|f| f.get("geometry"))
+            .unwrap();
+        let span = geometry.span().unwrap();
+        assert!(span.as_str().starts_with('{') && span.as_str().ends_with('}'));
+        assert_eq!(
+            json_parser().parse(span.as_str()).as_ref(),
+            full.pointer("/features/2/geometry")
+        );
+        assert_eq!(cursor.span().unwrap().as_str(), src.trim());
+    }
+
+    #[test]
+    fn test_cursor_iterators() {
+        let src = r#"{"a": [1, "two", [3], {"x": null}, true], "b!": false, "c": {}}"#;
+        let cursor = JsonCursor::new(src);
+
+        let kinds: Vec<_> = cursor
+            .get("a")
+            .unwrap()
+            .elements()
+            .map(|e| e.kind().unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                JsonKind::Number,
+                JsonKind::String,
+                JsonKind::Array,
+                JsonKind::Object,
+                JsonKind::Bool
+            ]
+        );
+
+        let keys: Vec<_> = cursor.entries().map(|(k, _)| k).collect();
+        assert_eq!(keys, ["a", "b!", "c"]);
+        assert_eq!(cursor.get("b!").and_then(|b| b.as_bool()), Some(false));
+        assert!(
+            cursor
+                .get("a")
+                .unwrap()
+                .index(3)
+                .unwrap()
+                .get("x")
+                .unwrap()
+                .is_null()
+        );
+
+        assert_eq!(cursor.get("c").unwrap().entries().count(), 0);
+        assert_eq!(JsonCursor::new("[ ]").elements().count(), 0);
+    }
+
+    #[test]
+    fn test_cursor_misses() {
+        let src = r#"{"a": [1, 2], "s": "[not] {an} array"}"#;
+        let cursor = JsonCursor::new(src);
+        assert!(cursor.get("missing").is_none());
+        assert!(cursor.index(0).is_none());
+        assert!(cursor.get("a").unwrap().index(2).is_none());
+        assert!(cursor.get("a").unwrap().get("0").is_none());
+        assert!(cursor.get("s").unwrap().elements().next().is_none());
+        assert_eq!(
+            cursor.get("s").unwrap().span().map(|s| s.as_str()),
+            Some(r#""[not] {an} array""#)
+        );
+        assert!(cursor.get("a").unwrap().as_str().is_none());
+        assert!(JsonCursor::new("").kind().is_none());
+        assert!(JsonCursor::new("[1, 2").span().is_none());
+    }
+
+    #[test]
+    fn test_cursor_value_borrows() {
+        let src = r#"{"k": ["plain", "esc\naped"]}"#;
+        let arr = JsonCursor::new(src).get("k").unwrap();
+        assert_eq!(
+            arr.value(),
+            Some(JsonValue::Array(Box::new(vec![
+                JsonValue::String(Cow::Borrowed("plain")),
+                JsonValue::String(Cow::Owned("esc\naped".to_string())),
+            ])))
+        );
+        assert!(matches!(
+            arr.index(0).unwrap().as_str(),
+            Some(Cow::Borrowed("plain"))
+        ));
+    }
+}
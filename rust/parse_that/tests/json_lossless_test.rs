@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::json::{JsonValue, json_parser};
+    use parse_that::parsers::json_lossless::{JsonNumber, JsonValueLossless, json_parser_lossless};
+    use std::fs;
+
+    fn numbers<'a>(value: &JsonValueLossless<'a>) -> Vec<JsonNumber<'a>> {
+        match value {
+            JsonValueLossless::Array(items) => items
+                .iter()
+                .map(|v| match v {
+                    JsonValueLossless::Number(n) => *n,
+                    other => panic!("expected Number, got {other:?}"),
+                })
+                .collect(),
+            other => panic!("expected Array, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_lossless_integers() {
+        let src = "[9007199254740993, -9223372036854775808, 18446744073709551615, 123456789012345678901234567890, 0]";
+        let value = json_parser_lossless().parse(src).unwrap();
+        let nums = numbers(&value);
+        assert_eq!(nums[0], JsonNumber::I64(9007199254740993));
+        assert_eq!(nums[1], JsonNumber::I64(i64::MIN));
+        assert_eq!(nums[2], JsonNumber::U64(u64::MAX));
+        assert!(
+            matches!(nums[3], JsonNumber::Raw(s) if s.as_str() == "123456789012345678901234567890")
+        );
+        assert_eq!(nums[4], JsonNumber::I64(0));
+
+        assert_eq!(nums[0].as_i64(), Some(9007199254740993));
+        assert_eq!(nums[2].as_i64(), None);
+        assert_eq!(nums[2].as_u64(), Some(u64::MAX));
+        assert_eq!(nums[1].as_u64(), None);
+        assert_eq!(nums[3].as_f64(), 1.2345678901234568e29);
+
+        // The f64 parser cannot tell 2^53 + 1 from 2^53
+        assert_eq!(
+            json_parser().parse("9007199254740993"),
+            Some(JsonValue::Number(9007199254740992.0))
+        );
+    }
+
+    #[test]
+    fn test_lossless_floats() {
+        let value = json_parser_lossless()
+            .parse("[0.1, -2.5, 1e21, 1.50, 1E3, 2.0, -0, 1e400]")
+            .unwrap();
+        let nums = numbers(&value);
+        assert_eq!(nums[0], JsonNumber::F64(0.1));
+        assert_eq!(nums[1], JsonNumber::F64(-2.5));
+        assert_eq!(nums[2], JsonNumber::F64(1e21));
+        for (n, text) in nums[3..6].iter().zip(["1.50", "1E3", "2.0"]) {
+            assert!(
+                matches!(n, JsonNumber::Raw(s) if s.as_str() == text),
+                "{n:?}"
+            );
+        }
+        assert!(matches!(nums[6], JsonNumber::F64(z) if z == 0.0 && z.is_sign_negative()));
+        assert!(matches!(nums[7], JsonNumber::Raw(_)));
+        assert_eq!(nums[7].as_f64(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_lossless_reproduces_digits() {
+        let src = r#"{"id":18446744073709551615,"n":[1.50,-0,1E+3,0.30000000000000004,123456789012345678901234567890],"s":"é"}"#;
+        let value = json_parser_lossless().parse(src).unwrap();
+        assert_eq!(
+            value.to_json_string(),
+            r#"{"id":18446744073709551615,"n":[1.50,-0,1E+3,0.30000000000000004,123456789012345678901234567890],"s":"é"}"#
+        );
+        assert_ne!(
+            json_parser().parse(src).unwrap().to_json_string(),
+            value.to_json_string()
+        );
+    }
+
+    #[test]
+    fn test_lossless_corpora() {
+        for file in [
+            "data.json",
+            "canada.json",
+            "twitter.json",
+            "citm_catalog.json",
+        ] {
+            let src = fs::read_to_string(format!("../../data/json/{file}")).unwrap();
+            let value = json_parser_lossless().parse(&src).unwrap();
+            let out = value.to_json_string();
+            assert_eq!(
+                json_parser_lossless().parse(&out),
+                Some(value.clone()),
+                "{file}"
+            );
+            assert_eq!(
+                JsonValue::from(value),
+                json_parser().parse(&src).unwrap(),
+                "{file}"
+            );
+        }
+    }
+}
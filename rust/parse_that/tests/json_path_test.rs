@@ -0,0 +1,225 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::json::{JsonValue, json_parser};
+    use parse_that::parsers::json_path::{JsonPath, Segment, Selector};
+    use std::borrow::Cow;
+
+    const STORE: &str = r#"{
+        "store": {
+            "book": [
+                {"category": "reference", "author": "Nigel Rees", "title": "Sayings of the Century", "price": 8.95},
+                {"category": "fiction", "author": "Evelyn Waugh", "title": "Sword of Honour", "price": 12.99},
+                {"category": "fiction", "author": "Herman Melville", "title": "Moby Dick", "isbn": "0-553-21311-3", "price": 8.99},
+                {"category": "fiction", "author": "J. R. R. Tolkien", "title": "The Lord of the Rings", "isbn": "0-395-19395-8", "price": 22.99}
+            ],
+            "bicycle": {"color": "red", "price": 19.95}
+        },
+        "a/b": 1,
+        "m~n": 2,
+        "": 3
+    }"#;
+
+    fn strings<'v>(values: &[&'v JsonValue<'_>]) -> Vec<&'v str> {
+        values
+            .iter()
+            .map(|v| match v {
+                JsonValue::String(s) => s.as_ref(),
+                other => panic!("expected String, got {other:?}"),
+            })
+            .collect()
+    }
+
+    fn numbers(values: &[&JsonValue<'_>]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|v| match v {
+                JsonValue::Number(n) => *n,
+                other => panic!("expected Number, got {other:?}"),
+            })
+            .collect()
+    }
+
+    // ── JSON Pointer ────────────────────────────────────────────────
+
+    #[test]
+    fn test_pointer_lookup() {
+        let doc = json_parser().parse(STORE).unwrap();
+        assert_eq!(doc.pointer(""), Some(&doc));
+        assert_eq!(
+            doc.pointer("/store/book/0/author"),
+            Some(&JsonValue::String(Cow::Borrowed("Nigel Rees")))
+        );
+        assert_eq!(
+            doc.pointer("/store/bicycle/price"),
+            Some(&JsonValue::Number(19.95))
+        );
+        assert_eq!(doc.pointer("/a~1b"), Some(&JsonValue::Number(1.0)));
+        assert_eq!(doc.pointer("/m~0n"), Some(&JsonValue::Number(2.0)));
+        assert_eq!(doc.pointer("/"), Some(&JsonValue::Number(3.0)));
+    }
+
+    #[test]
+    fn test_pointer_misses() {
+        let doc = json_parser().parse(STORE).unwrap();
+        for ptr in [
+            "store",
+            "/missing",
+            "/store/book/4",
+            "/store/book/-",
+            "/store/book/01",
+            "/store/book/+1",
+            "/store/bicycle/color/x",
+        ] {
+            assert_eq!(doc.pointer(ptr), None, "{ptr}");
+        }
+    }
+
+    #[test]
+    fn test_pointer_mut() {
+        let mut doc = json_parser().parse(STORE).unwrap();
+        *doc.pointer_mut("/store/bicycle/color").unwrap() = JsonValue::Bool(true);
+        assert_eq!(
+            doc.pointer("/store/bicycle/color"),
+            Some(&JsonValue::Bool(true))
+        );
+        assert!(doc.pointer_mut("/nope").is_none());
+    }
+
+    // ── JSONPath ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_path_children_and_wildcards() {
+        let doc = json_parser().parse(STORE).unwrap();
+        assert_eq!(
+            strings(&doc.query("$.store.book[*].author").unwrap()),
+            [
+                "Nigel Rees",
+                "Evelyn Waugh",
+                "Herman Melville",
+                "J. R. R. Tolkien"
+            ]
+        );
+        assert_eq!(doc.query("$.store.*").unwrap().len(), 2);
+        assert_eq!(
+            strings(&doc.query("$['store']['bicycle'][\"color\"]").unwrap()),
+            ["red"]
+        );
+        assert_eq!(doc.query("$").unwrap(), [&doc]);
+    }
+
+    #[test]
+    fn test_path_descendants() {
+        let doc = json_parser().parse(STORE).unwrap();
+        assert_eq!(doc.query("$..author").unwrap().len(), 4);
+        assert_eq!(
+            numbers(&doc.query("$.store..price").unwrap()),
+            [8.95, 12.99, 8.99, 22.99, 19.95]
+        );
+        assert_eq!(
+            strings(&doc.query("$..book[2].title").unwrap()),
+            ["Moby Dick"]
+        );
+    }
+
+    #[test]
+    fn test_path_indices_and_slices() {
+        let doc = json_parser().parse(STORE).unwrap();
+        let titles = |path: &str| {
+            doc.query(path)
+                .unwrap()
+                .iter()
+                .map(|v| v.pointer("/title").unwrap())
+                .map(|v| match v {
+                    JsonValue::String(s) => s.to_string(),
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles("$.store.book[-1]"), ["The Lord of the Rings"]);
+        assert_eq!(
+            titles("$.store.book[0:2]"),
+            ["Sayings of the Century", "Sword of Honour"]
+        );
+        assert_eq!(
+            titles("$.store.book[-2:]"),
+            ["Moby Dick", "The Lord of the Rings"]
+        );
+        assert_eq!(titles("$.store.book[:1]"), ["Sayings of the Century"]);
+        assert_eq!(
+            titles("$.store.book[0, 3]"),
+            ["Sayings of the Century", "The Lord of the Rings"]
+        );
+        assert!(titles("$.store.book[9]").is_empty());
+    }
+
+    #[test]
+    fn test_path_filters() {
+        let doc = json_parser().parse(STORE).unwrap();
+        let authors = |path: &str| {
+            strings(
+                &doc.query(&format!("{path}.author"))
+                    .unwrap_or_else(|e| panic!("{path}: {e}")),
+            )
+            .into_iter()
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            authors("$.store.book[?(@.price < 10)]"),
+            ["Nigel Rees", "Herman Melville"]
+        );
+        assert_eq!(
+            authors("$.store.book[?(@.isbn)]"),
+            ["Herman Melville", "J. R. R. Tolkien"]
+        );
+        assert_eq!(
+            authors("$..book[?(@.category == 'fiction' && @.price >= 20)]"),
+            ["J. R. R. Tolkien"]
+        );
+        assert_eq!(
+            authors("$..book[?@.category != \"fiction\" || @.price == 8.99]"),
+            ["Nigel Rees", "Herman Melville"]
+        );
+        assert_eq!(
+            authors("$.store.book[?!(@.isbn)]"),
+            ["Nigel Rees", "Evelyn Waugh"]
+        );
+        assert_eq!(
+            authors("$.store.book[?(10 > @.price)]"),
+            ["Nigel Rees", "Herman Melville"]
+        );
+        // Type mismatches never match ordering comparisons
+        assert!(authors("$.store.book[?(@.title < 5)]").is_empty());
+    }
+
+    #[test]
+    fn test_path_ast() {
+        let path = JsonPath::parse("$.a..b[0]").unwrap();
+        assert_eq!(
+            path.segments,
+            [
+                Segment::Child(Selector::Name(Cow::Borrowed("a"))),
+                Segment::Descendant(Selector::Name(Cow::Borrowed("b"))),
+                Segment::Child(Selector::Index(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_syntax_errors() {
+        for bad in [
+            "",
+            "store",
+            "$.",
+            "$[",
+            "$[?(@.a ==)]",
+            "$.a b",
+            "$[1",
+            "$[?(1)]",
+        ] {
+            assert!(JsonPath::parse(bad).is_err(), "{bad:?}");
+        }
+        let doc = json_parser().parse(STORE).unwrap();
+        assert!(doc.query("$..[").is_err());
+    }
+}
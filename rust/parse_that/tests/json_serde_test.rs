@@ -0,0 +1,147 @@
+#[cfg(feature = "serde")]
+mod tests {
+    use parse_that::parsers::json_serde::from_str;
+    use serde::Deserialize;
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Person<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        nick: Cow<'a, str>,
+        age: u32,
+        score: f64,
+        tags: Vec<String>,
+        email: Option<&'a str>,
+    }
+
+    #[test]
+    fn test_struct_borrowed_fields() {
+        let src = r#"{
+            "name": "Ada",
+            "nick": "the \"countess\"",
+            "age": 36,
+            "score": -1.5e2,
+            "tags": ["math", "engines"],
+            "email": null
+        }"#;
+        let p: Person = from_str(src).unwrap();
+        assert_eq!(p.name, "Ada");
+        assert_eq!(p.nick, "the \"countess\"");
+        assert!(matches!(p.nick, Cow::Owned(_)));
+        assert_eq!(p.age, 36);
+        assert_eq!(p.score, -150.0);
+        assert_eq!(p.tags, vec!["math", "engines"]);
+        assert_eq!(p.email, None);
+
+        // The borrowed field points into the source buffer.
+        let range = src.as_bytes().as_ptr_range();
+        assert!(range.contains(&p.name.as_ptr()));
+    }
+
+    #[test]
+    fn test_borrowed_str_rejects_escapes() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct S<'a> {
+            s: &'a str,
+        }
+        assert!(from_str::<S>(r#"{"s": "a\nb"}"#).is_err());
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(from_str::<i64>("-9223372036854775808").unwrap(), i64::MIN);
+        assert_eq!(from_str::<u64>("18446744073709551615").unwrap(), u64::MAX);
+        assert_eq!(from_str::<u8>("255").unwrap(), 255);
+        assert!(from_str::<u8>("256").is_err());
+        assert_eq!(from_str::<f64>("12345678901234567890").unwrap(), 1.2345678901234567e19);
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Point(i32, i32),
+        Rect { w: u32, h: u32 },
+    }
+
+    #[test]
+    fn test_enums() {
+        let shapes: Vec<Shape> = from_str(
+            r#"["Empty", {"Circle": 2.5}, {"Point": [1, -2]}, {"Rect": {"w": 3, "h": 4}}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            shapes,
+            vec![
+                Shape::Empty,
+                Shape::Circle(2.5),
+                Shape::Point(1, -2),
+                Shape::Rect { w: 3, h: 4 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_maps_and_unknown_fields() {
+        #[derive(Debug, Deserialize)]
+        struct Config<'a> {
+            #[serde(borrow)]
+            env: HashMap<&'a str, &'a str>,
+        }
+        let c: Config = from_str(
+            r#"{"ignored": [1, {"deep": [true, false, null]}], "env": {"A": "1", "B": "2"}}"#,
+        )
+        .unwrap();
+        assert_eq!(c.env.len(), 2);
+        assert_eq!(c.env["B"], "2");
+    }
+
+    #[test]
+    fn test_matches_serde_json_value() {
+        let src = r#"{"a": [1, 2.5, "xé", {"b": null}], "c": true, "d": -7}"#;
+        let ours: serde_json::Value = from_str(src).unwrap();
+        let theirs: serde_json::Value = serde_json::from_str(src).unwrap();
+        assert_eq!(ours, theirs);
+    }
+
+    #[test]
+    fn test_errors_carry_location() {
+        let err = from_str::<Vec<u32>>("[1, 2,\n  x]").unwrap_err();
+        assert_eq!(err.line(), 2);
+        assert_eq!(err.column(), 2);
+        assert_eq!(err.offset(), 9);
+
+        // Type errors raised by the visitor are located too.
+        let err = from_str::<Vec<u32>>("[1,\n \"two\"]").unwrap_err();
+        assert_eq!(err.line(), 2);
+        assert!(err.message().contains("invalid type"), "{}", err.message());
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        assert!(from_str::<serde_json::Value>("[1 2]").is_err());
+        assert!(from_str::<serde_json::Value>("[1,]").is_err());
+        assert!(from_str::<serde_json::Value>(r#"{"a" 1}"#).is_err());
+        assert!(from_str::<serde_json::Value>(r#"{1: 2}"#).is_err());
+        assert!(from_str::<serde_json::Value>("nul").is_err());
+        assert!(from_str::<serde_json::Value>("[1] x").is_err());
+        assert!(from_str::<serde_json::Value>("").is_err());
+    }
+
+    #[test]
+    fn test_recursion_limit() {
+        let nested = |n: usize| format!("{}{}", "[".repeat(n), "]".repeat(n));
+        assert!(from_str::<serde::de::IgnoredAny>(&nested(128)).is_ok());
+        let err = from_str::<serde::de::IgnoredAny>(&nested(129)).unwrap_err();
+        assert_eq!(err.message(), "recursion limit exceeded");
+        assert_eq!(err.offset(), 128);
+
+        let err = from_str::<serde::de::IgnoredAny>(&"[".repeat(200_000)).unwrap_err();
+        assert_eq!(err.message(), "recursion limit exceeded");
+        let err = from_str::<serde_json::Value>(&r#"{"a":"#.repeat(200_000)).unwrap_err();
+        assert_eq!(err.message(), "recursion limit exceeded");
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::json::JsonValue;
+    use parse_that::parsers::json_stream::{JsonEvent, JsonStreamError, JsonStreamParser};
+    use std::borrow::Cow;
+
+    /// Feed `src` in `chunk`-byte pieces, draining events after each feed.
+    /// Events are rendered with `Debug` so they can outlive the parser buffer.
+    fn stream(src: &[u8], chunk: usize) -> Result<Vec<String>, JsonStreamError> {
+        let mut parser = JsonStreamParser::new();
+        let mut events = Vec::new();
+        let mut drain = |parser: &mut JsonStreamParser| -> Result<(), JsonStreamError> {
+            while let Some(event) = parser.next_event()? {
+                events.push(format!("{event:?}"));
+            }
+            Ok(())
+        };
+        for piece in src.chunks(chunk.max(1)) {
+            parser.feed(piece)?;
+            drain(&mut parser)?;
+        }
+        parser.finish();
+        drain(&mut parser)?;
+        Ok(events)
+    }
+
+    fn events(src: &str) -> Vec<String> {
+        stream(src.as_bytes(), usize::MAX).unwrap()
+    }
+
+    const DOC: &str = r#" {"name": "caf\u00e9 ☕", "tags": ["a\"b", "", "日本"],
+        "n": [-12.5e-3, 0, 1234567890123, true, false, null],
+        "nested": {"empty": {}, "list": [[], [{}]]}, "esc\\key": "\ud83d\ude00"} "#;
+
+    #[test]
+    fn test_events_for_document() {
+        let evs = events(r#"{"a": [1, "x", null], "b": {"c": true}}"#);
+        let expected = [
+            JsonEvent::StartObject,
+            JsonEvent::Key(Cow::Borrowed("a")),
+            JsonEvent::StartArray,
+            JsonEvent::Value(JsonValue::Number(1.0)),
+            JsonEvent::Value(JsonValue::String(Cow::Borrowed("x"))),
+            JsonEvent::Value(JsonValue::Null),
+            JsonEvent::EndArray,
+            JsonEvent::Key(Cow::Borrowed("b")),
+            JsonEvent::StartObject,
+            JsonEvent::Key(Cow::Borrowed("c")),
+            JsonEvent::Value(JsonValue::Bool(true)),
+            JsonEvent::EndObject,
+            JsonEvent::EndObject,
+        ]
+        .map(|e| format!("{e:?}"));
+        assert_eq!(evs, expected);
+    }
+
+    #[test]
+    fn test_every_chunk_size_matches() {
+        let whole = events(DOC);
+        assert!(whole.contains(&format!(
+            "{:?}",
+            JsonEvent::Key(Cow::Owned("esc\\key".into()))
+        )));
+        assert!(whole.contains(&format!(
+            "{:?}",
+            JsonEvent::Value(JsonValue::String(Cow::Owned("café ☕".into())))
+        )));
+        for chunk in 1..DOC.len() {
+            assert_eq!(
+                stream(DOC.as_bytes(), chunk).unwrap(),
+                whole,
+                "chunk {chunk}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tokens_held_until_complete() {
+        let mut parser = JsonStreamParser::new();
+        parser.feed(b"[12").unwrap();
+        assert_eq!(parser.next_event(), Ok(Some(JsonEvent::StartArray)));
+        assert_eq!(parser.next_event(), Ok(None));
+        parser.feed(b"34, tr").unwrap();
+        assert_eq!(
+            parser.next_event(),
+            Ok(Some(JsonEvent::Value(JsonValue::Number(1234.0))))
+        );
+        assert_eq!(parser.next_event(), Ok(None));
+        parser.feed(b"ue, \"\xE2\x98").unwrap();
+        assert_eq!(
+            parser.next_event(),
+            Ok(Some(JsonEvent::Value(JsonValue::Bool(true))))
+        );
+        assert_eq!(parser.next_event(), Ok(None));
+        parser.feed(b"\x95\"]").unwrap();
+        assert_eq!(
+            parser.next_event(),
+            Ok(Some(JsonEvent::Value(JsonValue::String(Cow::Borrowed(
+                "☕"
+            )))))
+        );
+        assert_eq!(parser.next_event(), Ok(Some(JsonEvent::EndArray)));
+        assert_eq!(parser.depth(), 0);
+        parser.finish();
+        assert_eq!(parser.next_event(), Ok(None));
+    }
+
+    #[test]
+    fn test_top_level_number_needs_finish() {
+        let mut parser = JsonStreamParser::new();
+        parser.feed(b"42").unwrap();
+        assert_eq!(parser.next_event(), Ok(None));
+        parser.finish();
+        assert_eq!(
+            parser.next_event(),
+            Ok(Some(JsonEvent::Value(JsonValue::Number(42.0))))
+        );
+        assert_eq!(parser.next_event(), Ok(None));
+    }
+
+    #[test]
+    fn test_ndjson_values() {
+        let evs = events("{\"id\": 1}\n{\"id\": 2}\n\"tail\"\n");
+        assert_eq!(evs.iter().filter(|e| *e == "StartObject").count(), 2);
+        assert_eq!(evs.last().unwrap(), "Value(String(\"tail\"))");
+    }
+
+    #[test]
+    fn test_stream_errors() {
+        let cases: [(&[u8], usize, &str); 12] = [
+            (b"[1 2]", 3, "`,` or closing bracket"),
+            (b"{\"a\" 1}", 5, "`:`"),
+            (b"{1: 2}", 1, "string key"),
+            (b"[1,]", 3, "value"),
+            (b"[\"\\x\"]", 1, "valid escape sequence"),
+            (b"[nul]", 1, "value"),
+            (b"[007]", 1, "number"),
+            (b"1-2", 0, "number"),
+            (b"[1", 2, "more input"),
+            (b"\"open", 5, "closing quote"),
+            (b"[\xFF]", 1, "valid UTF-8"),
+            (b"\"\xE2\x98", 1, "valid UTF-8"),
+        ];
+        for (src, offset, expected) in cases {
+            for chunk in [1, 2, src.len()] {
+                let err = stream(src, chunk).unwrap_err();
+                assert_eq!(
+                    (err.offset, err.expected),
+                    (offset, expected),
+                    "{:?} chunk {chunk}",
+                    String::from_utf8_lossy(src)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_is_sticky_and_offsets_are_absolute() {
+        let mut parser = JsonStreamParser::new();
+        parser.feed(b"[true, ").unwrap();
+        while parser.next_event().unwrap().is_some() {}
+        parser.feed(b"  ]").unwrap();
+        let err = parser.next_event().unwrap_err();
+        assert_eq!(err.offset, 9);
+        assert_eq!(parser.next_event(), Err(err.clone()));
+        assert_eq!(parser.feed(b"1"), Err(err));
+    }
+}
@@ -2,6 +2,10 @@
 mod tests {
     use parse_that::parsers::json::JsonValue;
     use parse_that::parsers::json::json_parser;
+    use parse_that::parsers::json::{
+        JSON_STRICT_MAX_DEPTH, json_parser_strict, json_parser_strict_with_depth,
+    };
+    use parse_that::state::LimitError;
     use std::borrow::Cow;
     use std::fs;
 
@@ -70,22 +74,29 @@ mod tests {
         println!("{}", pretty);
     }
 
-    // ── String edge case tests (combinator path — raw spans) ────────
+    // ── String edge case tests ──────────────────────────────────────
 
     #[test]
     fn test_string_basic_escapes() {
         let cases = vec![
-            r#""\"""#, r#""\\""#, r#""\/""#, r#""\b""#, r#""\n""#, r#""\r""#, r#""\t""#,
+            (r#""\"""#, "\""),
+            (r#""\\""#, "\\"),
+            (r#""\/""#, "/"),
+            (r#""\b""#, "\u{08}"),
+            (r#""\f""#, "\u{0C}"),
+            (r#""\n""#, "\n"),
+            (r#""\r""#, "\r"),
+            (r#""\t""#, "\t"),
         ];
 
         let parser = json_parser();
-        for input in &cases {
+        for (input, expected) in &cases {
             let result = parser.parse(input);
             assert!(result.is_some(), "Failed to parse string: {}", input);
             match result.unwrap() {
                 JsonValue::String(s) => {
-                    // Combinator path returns raw span — just verify it parsed
-                    assert!(!s.is_empty());
+                    assert_eq!(s.as_ref(), *expected, "Wrong decoding for {}", input);
+                    assert!(matches!(s, Cow::Owned(_)), "Escaped string should be owned");
                 }
                 other => panic!("Expected String, got {:?} for input {}", other, input),
             }
@@ -98,14 +109,81 @@ mod tests {
         let result = json_parser().parse(input);
         assert!(result.is_some(), "Failed to parse unicode escape");
         match result.unwrap() {
-            JsonValue::String(s) => {
-                // Combinator path returns raw span
-                assert_eq!(s.as_ref(), r"\u0041");
-            }
+            JsonValue::String(s) => assert_eq!(s.as_ref(), "A"),
             other => panic!("Expected String, got {:?}", other),
         }
     }
 
+    #[test]
+    fn test_string_surrogate_pair_decoded() {
+        let input = r#""smile \uD83D\uDE00!""#;
+        match json_parser().parse(input).unwrap() {
+            JsonValue::String(s) => assert_eq!(s.as_ref(), "smile \u{1F600}!"),
+            other => panic!("Expected String, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_string_unescaped_is_borrowed() {
+        let input = r#"{"key": "plain välue"}"#;
+        match json_parser().parse(input).unwrap() {
+            JsonValue::Object(pairs) => {
+                assert!(matches!(pairs[0].0, Cow::Borrowed("key")));
+                assert!(matches!(pairs[0].1, JsonValue::String(Cow::Borrowed("plain välue"))));
+            }
+            other => panic!("Expected Object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_string_escaped_key_lookup() {
+        let input = r#"{"a\"b": 1, "line\nbreak": "x\ty"}"#;
+        match json_parser().parse(input).unwrap() {
+            JsonValue::Object(pairs) => {
+                assert_eq!(obj_get(&pairs, "a\"b"), Some(&JsonValue::Number(1.0)));
+                assert_eq!(
+                    obj_get(&pairs, "line\nbreak"),
+                    Some(&JsonValue::String(Cow::Borrowed("x\ty")))
+                );
+            }
+            other => panic!("Expected Object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_string_matches_serde() {
+        let cases = [
+            r#""\u00e9t\u00E9""#,
+            r#""tab\tand\\backslash""#,
+            r#""\uD834\uDD1E clef""#,
+            r#""mixed ünïcode \u2603 \"quoted\"""#,
+        ];
+        for input in cases {
+            let theirs: String = serde_json::from_str(input).unwrap();
+            match json_parser().parse(input).unwrap() {
+                JsonValue::String(s) => assert_eq!(s.as_ref(), theirs, "Mismatch for {input}"),
+                other => panic!("Expected String for {input}, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unescape_rejects_invalid() {
+        assert_eq!(
+            json_parser().parse(r#""a\nb""#),
+            Some(JsonValue::String("a\nb".into()))
+        );
+        for input in [r#""\x""#, r#""\u12""#, r#""\uDC00""#, r#""\uD800\u0041""#] {
+            assert!(json_parser().parse(input).is_none(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_malformed_invalid_escape() {
+        assert!(json_parser().parse(r#""\x""#).is_none());
+        assert!(json_parser().parse(r#""\'""#).is_none());
+    }
+
     #[test]
     fn test_string_empty() {
         let input = r#""""#;
@@ -364,9 +442,7 @@ mod tests {
                     b_f64
                 );
             }
-            (JsonValue::String(_), serde_json::Value::String(_)) => {
-                // Combinator path returns raw spans — skip string content comparison
-            }
+            (JsonValue::String(a), serde_json::Value::String(b)) => assert_eq!(a, b),
             (JsonValue::Array(a), serde_json::Value::Array(b)) => {
                 assert_eq!(a.len(), b.len(), "Array length mismatch");
                 for (ai, bi) in a.iter().zip(b.iter()) {
@@ -490,4 +566,221 @@ mod tests {
             other => panic!("expected Number, got {:?}", other),
         }
     }
+
+    // ── Strict RFC 8259 parser ──────────────────────────────────────
+
+    fn read_vectors(filename: &str) -> Vec<String> {
+        fs::read_to_string(format!("../../grammar/tests/json/{filename}"))
+            .unwrap()
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[test]
+    fn test_strict_valid_vectors() {
+        for line in read_vectors("valid.jsonl") {
+            assert!(json_parser_strict().parse(&line).is_some(), "strict rejected: {line}");
+        }
+    }
+
+    #[test]
+    fn test_strict_invalid_vectors() {
+        for line in read_vectors("invalid.jsonl") {
+            assert!(json_parser_strict().parse(&line).is_none(), "strict accepted: {line}");
+        }
+    }
+
+    #[test]
+    fn test_strict_accepts() {
+        let parser = json_parser_strict();
+        for src in [
+            "0",
+            "-0",
+            "-0.0e+0",
+            "1E10",
+            "1.5e-3",
+            "\"\"",
+            "\"\\u00e9\\ud83d\\ude00\"",
+            " [ ] ",
+            "{}",
+            "\n{\"a\" : [true, false, null]}\t",
+        ] {
+            assert!(parser.parse(src).is_some(), "strict rejected: {src:?}");
+        }
+    }
+
+    #[test]
+    fn test_strict_rejects() {
+        let parser = json_parser_strict();
+        for src in [
+            "",
+            "00",
+            "-01",
+            "+1",
+            "1e",
+            "1e+",
+            "-",
+            "0x10",
+            "\"\u{0}\"",
+            "\"a\nb\"",
+            "\"abc",
+            "[1 2]",
+            "[,1]",
+            "{\"a\" 1}",
+            "{\"a\":}",
+            "{1: 2}",
+            "tru",
+            "nul",
+            "{} {}",
+        ] {
+            assert!(parser.parse(src).is_none(), "strict accepted: {src:?}");
+        }
+    }
+
+    #[test]
+    fn test_strict_decodes_values() {
+        let value = json_parser_strict()
+            .parse(r#"{"k\"ey": [-1.5e2, "\u0041"]}"#)
+            .unwrap();
+        let JsonValue::Object(pairs) = value else {
+            panic!("expected Object");
+        };
+        assert_eq!(pairs[0].0, "k\"ey");
+        assert_eq!(
+            pairs[0].1,
+            JsonValue::Array(Box::new(vec![
+                JsonValue::Number(-150.0),
+                JsonValue::String(Cow::Borrowed("A")),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_strict_depth_limit() {
+        let nested = |n: usize| format!("{}{}", "[".repeat(n), "]".repeat(n));
+        let at_limit = nested(JSON_STRICT_MAX_DEPTH);
+        let over_limit = nested(JSON_STRICT_MAX_DEPTH + 1);
+        assert!(json_parser_strict().parse(&at_limit).is_some());
+        let err = json_parser_strict().parse_or_error(&over_limit).unwrap_err();
+        assert_eq!(err.limit_error, Some(LimitError::Depth));
+        assert_eq!(err.offset, JSON_STRICT_MAX_DEPTH);
+
+        let shallow = json_parser_strict_with_depth(2);
+        assert!(shallow.parse("{\"a\": [1]}").is_some());
+        assert!(shallow.parse("{\"a\": [[1]]}").is_none());
+        assert!(shallow.parse("{\"a\": [1]} x").is_none());
+        assert_eq!(
+            shallow.parse_or_error("{\"a\": [1]} x").unwrap_err().limit_error,
+            None
+        );
+    }
+
+    #[test]
+    fn test_lenient_parser_unchanged() {
+        // The lenient parser keeps accepting what strict mode rejects.
+        assert!(json_parser().parse("[1] x").is_some());
+        assert!(json_parser().parse("\"a\tb\"").is_some());
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn test_strict_error_labels() {
+        let parser = json_parser_strict();
+        let cases = [
+            ("[01]", "number without leading zeros", 2),
+            ("[1.]", "digit after decimal point", 3),
+            ("[1e]", "digit in exponent", 3),
+            ("[\"a\tb\"]", "escaped control character", 3),
+            ("[\"\\x\"]", "valid escape sequence", 2),
+            ("[1 2]", "`,` or `]`", 3),
+            ("{\"a\" 1}", "`:`", 5),
+            ("{\"a\": 1 \"b\"}", "`,` or `}`", 8),
+        ];
+        for (src, label, offset) in cases {
+            let err = parser.parse_or_error(src).unwrap_err();
+            assert!(
+                err.expected.iter().any(|e| e == label),
+                "{src:?}: expected {label:?}, got {:?}",
+                err.expected
+            );
+            assert_eq!(err.furthest_offset, offset, "{src:?}");
+        }
+    }
+
+    // ── JSON Lines ──────────────────────────────────────────────────
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn test_json_lines_recovers_per_line() {
+        use parse_that::parsers::json::json_lines_parser;
+
+        let src = "{\"id\": 1}\n\n[1, 2,]\r\n  \"ok\"  \n{\"id\": 4} trailing\n{\"a\":\n7";
+        let records = json_lines_parser().parse(src).unwrap();
+        assert_eq!(records.len(), 6);
+        assert_eq!(
+            records[0].as_ref().ok(),
+            Some(&JsonValue::Object(Box::new(vec![(
+                Cow::Borrowed("id"),
+                JsonValue::Number(1.0)
+            )])))
+        );
+        assert_eq!(
+            records[2].as_ref().ok(),
+            Some(&JsonValue::String(Cow::Borrowed("ok")))
+        );
+        assert_eq!(records[5].as_ref().ok(), Some(&JsonValue::Number(7.0)));
+
+        let errors: Vec<_> = records
+            .iter()
+            .filter_map(|r| r.as_ref().err())
+            .map(|d| (d.line, d.column, d.expected.clone()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (3, 6, vec!["value".to_string()]),
+                (5, 10, vec!["end of line".to_string()]),
+                (6, 5, vec!["value".to_string()]),
+            ]
+        );
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn test_json_lines_vector_files() {
+        use parse_that::parsers::json::json_lines_parser;
+
+        let valid = fs::read_to_string("../../grammar/tests/json/valid.jsonl").unwrap();
+        let records = json_lines_parser().parse(&valid).unwrap();
+        assert_eq!(records.len(), read_vectors("valid.jsonl").len());
+        assert!(records.iter().all(Result::is_ok));
+
+        let invalid = fs::read_to_string("../../grammar/tests/json/invalid.jsonl").unwrap();
+        let records = json_lines_parser().parse(&invalid).unwrap();
+        assert_eq!(records.len(), read_vectors("invalid.jsonl").len());
+        let lines: Vec<_> = records.iter().map(|r| r.as_ref().unwrap_err().line).collect();
+        let expected: Vec<_> = invalid
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, _)| i + 1)
+            .collect();
+        assert_eq!(lines, expected);
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn test_json_lines_many_bad_records() {
+        use parse_that::parsers::json::json_lines_parser;
+
+        let src = "{\"id\": 1}\n[1,]\n".repeat(20_000);
+        let records = json_lines_parser().parse(&src).unwrap();
+        assert_eq!(records.len(), 40_000);
+        for (i, record) in records.iter().enumerate().skip(1).step_by(2) {
+            let diagnostic = record.as_ref().unwrap_err();
+            assert_eq!((diagnostic.line, diagnostic.column), (i + 1, 3));
+        }
+    }
 }
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::json::{JsonValue, json_parser};
+    use parse_that::parsers::json_write::JsonFormat;
+    use std::borrow::Cow;
+    use std::fs;
+
+    fn round_trip(src: &str) {
+        let value = json_parser().parse(src).unwrap();
+        for format in [JsonFormat::Compact, JsonFormat::Indented(2)] {
+            let out = value.to_json_string_with(format);
+            assert_eq!(json_parser().parse(&out), Some(value.clone()), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn test_write_scalars() {
+        let cases = [
+            (JsonValue::Null, "null"),
+            (JsonValue::Bool(true), "true"),
+            (JsonValue::Number(1.0), "1"),
+            (JsonValue::Number(-0.5), "-0.5"),
+            (JsonValue::Number(0.1 + 0.2), "0.30000000000000004"),
+            (JsonValue::Number(1e21), "1e21"),
+            (
+                JsonValue::Number(123456789012345680000.0),
+                "123456789012345680000",
+            ),
+            (JsonValue::Number(1.5e-7), "1.5e-7"),
+            (JsonValue::Number(f64::NAN), "null"),
+            (
+                JsonValue::String(Cow::Borrowed("q\"b\\n\n\t\u{1}é☕")),
+                r#""q\"b\\n\n\t\u0001é☕""#,
+            ),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(value.to_json_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_write_layouts() {
+        let value = json_parser()
+            .parse(r#"{"a": [1, {"b": null}, []], "c": {}, "d": "x"}"#)
+            .unwrap();
+        assert_eq!(
+            value.to_json_string(),
+            r#"{"a":[1,{"b":null},[]],"c":{},"d":"x"}"#
+        );
+        assert_eq!(
+            value.to_json_string_with(JsonFormat::Indented(2)),
+            "{\n  \"a\": [\n    1,\n    {\n      \"b\": null\n    },\n    []\n  ],\n  \"c\": {},\n  \"d\": \"x\"\n}"
+        );
+        assert_eq!(
+            JsonValue::Array(Box::new(vec![JsonValue::Null]))
+                .to_json_string_with(JsonFormat::Indented(4)),
+            "[\n    null\n]"
+        );
+
+        let mut out = Vec::new();
+        value.write_json(&mut out).unwrap();
+        assert_eq!(out, value.to_json_string().into_bytes());
+    }
+
+    #[test]
+    fn test_round_trip_vectors() {
+        let vectors = fs::read_to_string("../../grammar/tests/json/valid.jsonl").unwrap();
+        for line in vectors.lines().filter(|l| !l.trim().is_empty()) {
+            round_trip(line);
+        }
+        round_trip(r#"["\u0000\u001f😀", 5e-324, 1.7976931348623157e308, -0]"#);
+    }
+
+    #[test]
+    fn test_round_trip_corpora() {
+        for file in [
+            "data.json",
+            "canada.json",
+            "apache-builds.json",
+            "twitter.json",
+            "citm_catalog.json",
+        ] {
+            let src = fs::read_to_string(format!("../../data/json/{file}")).unwrap();
+            round_trip(&src);
+        }
+    }
+}
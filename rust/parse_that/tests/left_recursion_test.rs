@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::*;
+
+    fn token<'a>(s: &'a str) -> Parser<'a, &'a str> {
+        string(s).trim_whitespace()
+    }
+
+    fn number<'a>() -> Parser<'a, i64> {
+        regex(r"\d+").trim_whitespace().map(|s| s.parse().unwrap())
+    }
+
+    fn apply((lhs, (op, rhs)): (i64, (&str, i64))) -> i64 {
+        match op {
+            "+" => lhs + rhs,
+            "-" => lhs - rhs,
+            "*" => lhs * rhs,
+            _ => lhs / rhs,
+        }
+    }
+
+    /// expr = expr ("+" | "-") term | term
+    /// term = term ("*" | "/") factor | factor
+    /// factor = number | "(" expr ")"
+    fn left_recursive_arithmetic<'a>() -> Parser<'a, i64> {
+        left_recursive(|expr: Rule<'a, i64>| {
+            let inner = expr.clone();
+            let factor = move || number() | inner.parser().wrap(token("("), token(")"));
+            let term = move || {
+                let factor = factor.clone();
+                left_recursive(move |term: Rule<'a, i64>| {
+                    term.parser()
+                        .then((token("*") | token("/")).then(factor()))
+                        .map(apply)
+                        | factor()
+                })
+            };
+            expr.parser()
+                .then((token("+") | token("-")).then(term()))
+                .map(apply)
+                | term()
+        })
+    }
+
+    /// The same grammar, hand-refactored into loops.
+    fn refactored_arithmetic<'a>() -> Parser<'a, i64> {
+        fn fold((first, rest): (i64, Vec<(&str, i64)>)) -> i64 {
+            rest.into_iter()
+                .fold(first, |lhs, (op, rhs)| apply((lhs, (op, rhs))))
+        }
+        fn expr<'a>() -> Parser<'a, i64> {
+            lazy(|| {
+                let factor = || number() | expr().wrap(token("("), token(")"));
+                let term = factor()
+                    .then((token("*") | token("/")).then(factor()).many(..))
+                    .map(fold);
+                term.then(
+                    (token("+") | token("-"))
+                        .then(
+                            factor()
+                                .then((token("*") | token("/")).then(factor()).many(..))
+                                .map(fold),
+                        )
+                        .many(..),
+                )
+                .map(fold)
+            })
+        }
+        expr()
+    }
+
+    #[test]
+    fn test_matches_refactored_grammar() {
+        let lr = left_recursive_arithmetic();
+        let loops = refactored_arithmetic();
+        for src in [
+            "1",
+            "1 + 2",
+            "10 - 3 - 2",
+            "100 / 10 / 5",
+            "2 * 3 + 4 * 5",
+            "2 * (3 + 4) * 5",
+            "((7))",
+            "1 - (2 - (3 - 4)) * 6 / 3",
+            "8 / 2 - 1 + 2 * 2 * 2 - 3",
+        ] {
+            let expected = loops.parse(src);
+            assert!(expected.is_some(), "{src}");
+            assert_eq!(lr.parse(src), expected, "{src}");
+        }
+    }
+
+    #[test]
+    fn test_left_associative_structure() {
+        let expr = left_recursive(|expr: Rule<'_, String>| {
+            expr.parser()
+                .skip(string("-"))
+                .then(regex("[a-z]"))
+                .map(|(lhs, rhs)| format!("({lhs}-{rhs})"))
+                | regex("[a-z]").map(str::to_string)
+        });
+        assert_eq!(expr.parse("a-b-c-d"), Some("(((a-b)-c)-d)".to_string()));
+        assert_eq!(expr.parse("a"), Some("a".to_string()));
+    }
+
+    #[test]
+    fn test_indirect_left_recursion() {
+        // a = b "x" | "y";  b = a
+        let a = left_recursive(|a: Rule<'_, usize>| {
+            let b = a.parser();
+            b.skip(string("x")).map(|n| n + 1) | string("y").map(|_| 0)
+        });
+        assert_eq!(a.parse("yxxx"), Some(3));
+        assert_eq!(a.parse("xy"), None);
+    }
+
+    #[test]
+    fn test_partial_match_and_failure() {
+        let expr = left_recursive_arithmetic();
+        let mut state = parse_that::state::ParserState::new("1 + 2 +");
+        assert_eq!(expr.call(&mut state), Some(3));
+        assert_eq!(state.offset, 6);
+        assert_eq!(expr.parse(""), None);
+        assert_eq!(expr.parse("+ 1"), None);
+        // The memo is per session: reuse on a fresh input is unaffected
+        assert_eq!(expr.parse("4 * 4"), Some(16));
+    }
+
+    #[test]
+    fn test_seed_survives_a_full_memo_table() {
+        use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+        // Each "(1)" enters the rule at a new offset, so one growth at 0
+        // caches more results than the memo keeps
+        let n = MEMO_DEFAULT_CAPACITY + 10;
+        let src = vec!["(1)"; n].join("-");
+        let passes = AtomicUsize::new(0);
+        let expr = left_recursive(|expr: Rule<'_, i64>| {
+            let term = || number() | expr.parser().wrap(token("("), token(")"));
+            let pass = Parser::new(|_: &mut ParserState<'_>| {
+                passes.fetch_add(1, Relaxed);
+                Some(())
+            });
+            pass.next(expr.parser().skip(token("-")).then(term()).map(|(a, b)| a - b) | term())
+        });
+        assert_eq!(expr.parse(&src), Some(2 - n as i64));
+        // Each "(1)" grows once inside the parens and once at 0: no regrowth
+        assert_eq!(passes.load(Relaxed), 3 * n + 3);
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::css::css_parser;
+    use parse_that::parsers::json::json_parser;
+    use parse_that::*;
+
+    /// s = "a" s "b" | "a" s "c" | "a"; exponential time on a run of "a"s.
+    fn backtracking<'a>() -> Parser<'a, usize> {
+        recursive(|s| {
+            let nested = |close: &'static str| {
+                string("a")
+                    .next(s.parser())
+                    .skip(string(close))
+                    .map(|n| n + 1)
+            };
+            nested("b") | nested("c") | string("a").map(|_| 0)
+        })
+    }
+
+    #[test]
+    fn test_depth_limit_on_json() {
+        let ok = format!("{}1{}", "[".repeat(32), "]".repeat(32));
+        let hostile = "[".repeat(1_000_000);
+        let parser = json_parser().max_depth(32);
+        assert!(parser.parse(&ok).is_some());
+        let err = parser.parse_or_error(&hostile).unwrap_err();
+        assert_eq!(err.limit_error, Some(LimitError::Depth));
+        assert_eq!(err.cut_offset, Some(33));
+        assert!(err.to_string().contains("nesting depth limit exceeded"));
+    }
+
+    #[test]
+    fn test_depth_limit_on_css() {
+        let nested = |n: usize| {
+            format!(
+                "{}a {{ color: red; }}{}",
+                "@media screen { ".repeat(n),
+                "}".repeat(n)
+            )
+        };
+        let (ok, hostile) = (nested(8), nested(100_000));
+        let parser = css_parser().max_depth(16);
+        assert!(parser.parse(&ok).is_some());
+        let err = parser.parse_or_error(&hostile).unwrap_err();
+        assert_eq!(err.limit_error, Some(LimitError::Depth));
+    }
+
+    #[test]
+    fn test_depth_limit_on_pratt_chains() {
+        let prefixes = format!("{}1", "-".repeat(1_000_000));
+        let powers = format!("1{}", "^1".repeat(1_000_000));
+        let number = || regex(r"\d+").map(|s| s.parse::<i64>().unwrap());
+        let expr = pratt(number())
+            .infix("^", Assoc::Right, 1, |a, b| a.max(b))
+            .prefix("-", 2, |a| -a)
+            .build()
+            .max_depth(64);
+        assert_eq!(expr.parse("- - -1 ^ 2 ^ 3"), Some(3));
+
+        let err = expr.parse_or_error(&prefixes).unwrap_err();
+        assert_eq!(err.limit_error, Some(LimitError::Depth));
+
+        let err = expr.parse_or_error(&powers).unwrap_err();
+        assert_eq!(err.limit_error, Some(LimitError::Depth));
+    }
+
+    #[test]
+    fn test_nested_depth_limits_take_the_tighter() {
+        let src = format!("{}1{}", "[".repeat(10), "]".repeat(10));
+        let parses = |inner, outer| {
+            let parser = json_parser().max_depth(inner).max_depth(outer);
+            parser.parse(&src).is_some()
+        };
+        assert!(!parses(8, 64));
+        assert!(!parses(64, 8));
+        assert!(parses(10, 64));
+    }
+
+    #[test]
+    fn test_fuel_bounds_backtracking() {
+        let hostile = "a".repeat(40);
+        let parser = backtracking().fuel(10_000);
+        assert_eq!(parser.parse("aab"), Some(1));
+        let err = parser.parse_or_error(&hostile).unwrap_err();
+        assert_eq!(err.limit_error, Some(LimitError::Fuel));
+        assert!(err.to_string().ends_with(": step budget exhausted"));
+        let source = std::error::Error::source(&err).unwrap();
+        assert_eq!(source.downcast_ref(), Some(&LimitError::Fuel));
+    }
+
+    #[test]
+    fn test_fuel_failure_is_not_backtracked() {
+        let parser = backtracking().fuel(100) | string("a").many(..).map(|a| a.len());
+        let mut state = ParserState::new("aaaaaaaaaaaaaaaaaaaa");
+        assert_eq!(parser.call(&mut state), None);
+        assert_eq!(state.limit_error, Some(LimitError::Fuel));
+    }
+
+    #[test]
+    fn test_fuel_counts_against_enclosing_budget() {
+        let src = "[[1], [2], [3]]";
+        let mut state = ParserState::new(src);
+        state.fuel = Some(100);
+        assert!(json_parser().fuel(1_000).call(&mut state).is_some());
+        assert_eq!(state.fuel, Some(94));
+
+        let mut state = ParserState::new(src);
+        state.fuel = Some(5);
+        assert!(json_parser().fuel(1_000).call(&mut state).is_none());
+        assert_eq!(state.limit_error, Some(LimitError::Fuel));
+    }
+
+    #[test]
+    fn test_reused_state_starts_clean() {
+        let parser = json_parser().max_depth(4);
+        let mut state = ParserState::new("[[[[[1]]]]] [1] x");
+        assert!(parser.call(&mut state).is_none());
+        assert_eq!(state.limit_error, Some(LimitError::Depth));
+
+        state.offset = 16;
+        assert!(parser.call(&mut state).is_none());
+        assert_eq!((state.limit_error, state.cut_offset), (None, None));
+
+        state.offset = 0;
+        assert!(parser.call(&mut state).is_none());
+        state.offset = 12;
+        assert!(json_parser().call(&mut state).is_some());
+        assert_eq!((state.limit_error, state.cut_offset), (None, None));
+    }
+
+    #[test]
+    fn test_no_limits_by_default() {
+        let (value, state) = backtracking().parse_return_state("aab");
+        assert_eq!(value, Some(1));
+        assert_eq!(state.limit_error, None);
+        assert_eq!(state.depth, 0);
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::state::{ParserState, Span};
+    use parse_that::*;
+
+    fn pos(line: usize, col: usize, char_col: usize, utf16_col: usize) -> LineCol {
+        LineCol {
+            line,
+            col,
+            char_col,
+            utf16_col,
+        }
+    }
+
+    #[test]
+    fn test_ascii_lines() {
+        let src = "ab\ncde\n\nf";
+        let index = LineIndex::new(src);
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line_col(0), pos(1, 0, 0, 0));
+        assert_eq!(index.line_col(2), pos(1, 2, 2, 2));
+        assert_eq!(index.line_col(3), pos(2, 0, 0, 0));
+        assert_eq!(index.line_col(7), pos(3, 0, 0, 0));
+        assert_eq!(index.line_col(9), pos(4, 1, 1, 1));
+        // Clamped to the end of the source
+        assert_eq!(index.line_col(100), pos(4, 1, 1, 1));
+
+        assert_eq!(index.line_range(2), Some(3..6));
+        assert_eq!(index.line_range(3), Some(7..7));
+        assert_eq!(index.line_range(0), None);
+        assert_eq!(index.line_range(5), None);
+
+        assert_eq!(index.offset(2, 3), Some(6));
+        assert_eq!(index.offset(2, 4), None);
+        assert_eq!(index.offset(4, 1), Some(9));
+    }
+
+    #[test]
+    fn test_round_trip_every_offset() {
+        let src = "fn main() {\n    let x = 1;\r\n}\n";
+        let index = LineIndex::new(src);
+        assert_eq!(index.line_count(), 4);
+        for offset in 0..=src.len() {
+            let lc = index.line_col(offset);
+            assert_eq!(index.offset(lc.line, lc.col), Some(offset));
+            assert_eq!(index.offset_char(lc.line, lc.char_col), Some(offset));
+            assert_eq!(index.offset_utf16(lc.line, lc.utf16_col), Some(offset));
+        }
+    }
+
+    #[test]
+    fn test_unicode_columns() {
+        // é: 2 bytes, 1 UTF-16 unit; 😀: 4 bytes, 2 UTF-16 units
+        let src = "x\nhé😀!\n😀";
+        let index = LineIndex::new(src);
+        let bang = src.find('!').unwrap();
+        assert_eq!(index.line_col(bang), pos(2, 7, 3, 4));
+        assert_eq!(index.line_col(src.len()), pos(3, 4, 1, 2));
+
+        assert_eq!(index.offset_char(2, 3), Some(bang));
+        assert_eq!(index.offset_utf16(2, 4), Some(bang));
+        assert_eq!(index.offset_utf16(2, 2), Some(src.find('😀').unwrap()));
+        // Between the halves of the surrogate pair
+        assert_eq!(index.offset_utf16(2, 3), None);
+        assert_eq!(index.offset_char(2, 5), None);
+        assert_eq!(index.offset_utf16(3, 2), Some(src.len()));
+    }
+
+    #[test]
+    fn test_span_line_cols() {
+        let src = "a\nbé c";
+        let index = LineIndex::new(src);
+        let span = Span::new(2, src.len(), src);
+        let (start, end) = span.line_cols(&index);
+        assert_eq!(start, pos(2, 0, 0, 0));
+        assert_eq!(end, pos(2, 5, 4, 4));
+    }
+
+    #[test]
+    fn test_state_uses_index() {
+        let src = "one\ntwo\nthree";
+        let mut state = ParserState::new(src);
+        state.offset = 10;
+        assert_eq!((state.get_line_number(), state.get_column_number()), (3, 2));
+        // The index is built once and reused at later offsets
+        let index: *const LineIndex = state.line_index();
+        state.offset = 5;
+        assert_eq!(state.line_col(), pos(2, 1, 1, 1));
+        assert!(std::ptr::eq(index, state.line_index()));
+
+        let word = regex("[a-z]+").skip(string("\n"));
+        let err = word.many(..).eof().parse_or_error(src).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 0));
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::state::ParserState;
+    use parse_that::*;
+
+    /// Operands are single letters; every fold renders its grouping.
+    fn render<'a>() -> Pratt<'a, String> {
+        pratt(regex("[a-z]").map(str::to_string))
+    }
+
+    fn arithmetic<'a>() -> Parser<'a, i64> {
+        lazy(|| {
+            let number = regex(r"\d+").map(|s| s.parse().unwrap());
+            let parens =
+                arithmetic().wrap(string("(").trim_whitespace(), string(")").trim_whitespace());
+            pratt(number | parens)
+                .infix("+", Assoc::Left, 1, |a, b| a + b)
+                .infix("-", Assoc::Left, 1, |a, b| a - b)
+                .infix("*", Assoc::Left, 2, |a, b| a * b)
+                .infix("/", Assoc::Left, 2, |a, b| a / b)
+                .prefix("-", 3, |a| -a)
+                .build()
+        })
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let expr = arithmetic();
+        for (src, expected) in [
+            ("1", 1),
+            ("1 + 2 * 3", 7),
+            ("(1 + 2) * 3", 9),
+            ("10 - 3 - 2", 5),
+            ("100 / 10 / 5", 2),
+            ("-2 * -(3 - 5)", -4),
+            ("8 / 2 - 1 + 2 * 2 * 2 - 3", 8),
+            ("1 - (2 - (3 - 4)) * 6 / 3", -5),
+        ] {
+            assert_eq!(expr.parse(src), Some(expected), "{src}");
+        }
+    }
+
+    #[test]
+    fn test_associativity() {
+        let expr = render()
+            .infix("-", Assoc::Left, 1, |a, b| format!("({a}-{b})"))
+            .infix("^", Assoc::Right, 2, |a, b| format!("({a}^{b})"))
+            .build();
+        assert_eq!(expr.parse("a-b-c").as_deref(), Some("((a-b)-c)"));
+        assert_eq!(expr.parse("a^b^c").as_deref(), Some("(a^(b^c))"));
+        assert_eq!(
+            expr.parse("a - b ^ c ^ d - e").as_deref(),
+            Some("((a-(b^(c^d)))-e)")
+        );
+    }
+
+    #[test]
+    fn test_prefix_postfix_precedence() {
+        let tight_postfix = render()
+            .prefix("-", 1, |a| format!("(-{a})"))
+            .postfix("!", 2, |a| format!("({a}!)"))
+            .infix("+", Assoc::Left, 0, |a, b| format!("({a}+{b})"))
+            .build();
+        assert_eq!(tight_postfix.parse("-a!").as_deref(), Some("(-(a!))"));
+        assert_eq!(
+            tight_postfix.parse("--a!!").as_deref(),
+            Some("(-(-((a!)!)))")
+        );
+        assert_eq!(
+            tight_postfix.parse("a! + -b").as_deref(),
+            Some("((a!)+(-b))")
+        );
+
+        let tight_prefix = render()
+            .prefix("-", 2, |a| format!("(-{a})"))
+            .postfix("!", 1, |a| format!("({a}!)"))
+            .build();
+        assert_eq!(tight_prefix.parse("-a!").as_deref(), Some("((-a)!)"));
+    }
+
+    #[test]
+    fn test_longest_operator_wins() {
+        let expr = render()
+            .infix("*", Assoc::Left, 1, |a, b| format!("({a}*{b})"))
+            .infix("**", Assoc::Right, 2, |a, b| format!("({a}**{b})"))
+            .infix("<", Assoc::None, 0, |a, b| format!("({a}<{b})"))
+            .infix("<=", Assoc::None, 0, |a, b| format!("({a}<={b})"))
+            .build();
+        assert_eq!(
+            expr.parse("a * b ** c ** d * e").as_deref(),
+            Some("((a*(b**(c**d)))*e)")
+        );
+        assert_eq!(expr.parse("a <= b*c").as_deref(), Some("(a<=(b*c))"));
+    }
+
+    #[test]
+    fn test_non_associative_rejected() {
+        let expr = render()
+            .infix("==", Assoc::None, 1, |a, b| format!("({a}=={b})"))
+            .infix("<", Assoc::None, 1, |a, b| format!("({a}<{b})"))
+            .infix("&", Assoc::Left, 0, |a, b| format!("({a}&{b})"))
+            .build();
+        assert_eq!(expr.parse("a == b").as_deref(), Some("(a==b)"));
+        assert_eq!(
+            expr.parse("a == b & c < d").as_deref(),
+            Some("((a==b)&(c<d))")
+        );
+        assert_eq!(expr.parse("a == b == c"), None);
+        assert_eq!(expr.parse("a == b < c"), None);
+    }
+
+    #[test]
+    fn test_stops_before_unknown_tokens() {
+        let expr = arithmetic();
+        let mut state = ParserState::new("1 + 2 ) + 3");
+        assert_eq!(expr.call(&mut state), Some(3));
+        assert_eq!(state.offset, 5);
+        assert_eq!(expr.parse("1 +"), None);
+        assert_eq!(expr.parse("* 2"), None);
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn test_error_labels() {
+        let expr = arithmetic().eof();
+        let err = expr.parse_or_error("1 + * 2").unwrap_err();
+        assert!(
+            err.expected.iter().any(|e| e == "operand"),
+            "{:?}",
+            err.expected
+        );
+        assert_eq!(err.furthest_offset, 4);
+
+        let cmp = render()
+            .infix("==", Assoc::None, 1, |a, b| format!("({a}=={b})"))
+            .build();
+        let err = cmp.parse_or_error("a == b == c").unwrap_err();
+        assert!(
+            err.expected
+                .iter()
+                .any(|e| e == "end of non-associative expression"),
+            "{:?}",
+            err.expected
+        );
+        assert_eq!(err.furthest_offset, 6);
+    }
+
+    #[test]
+    fn test_keyword_operators_need_a_word_boundary() {
+        let expr = pratt(regex("[a-z]+").trim_whitespace().map(str::to_string))
+            .infix("or", Assoc::Left, 1, |a, b| format!("({a} or {b})"))
+            .infix("and", Assoc::Left, 2, |a, b| format!("({a} and {b})"))
+            .prefix("not", 3, |a| format!("(not {a})"))
+            .infix("+", Assoc::Left, 4, |a, b| format!("({a}+{b})"))
+            .build();
+        assert_eq!(
+            expr.parse("a or b and c"),
+            Some("(a or (b and c))".to_string())
+        );
+        assert_eq!(expr.parse("a or order"), Some("(a or order)".to_string()));
+        assert_eq!(expr.parse("nothing"), Some("nothing".to_string()));
+        assert_eq!(expr.parse("a+b"), Some("(a+b)".to_string()));
+        // `android` is not `and` followed by `roid`.
+        let state = &mut ParserState::new("x android");
+        assert_eq!(expr.call(state), Some("x".to_string()));
+        assert_eq!(state.offset, 2);
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::css::{CssNode, CssSelector, css_parser};
+    use parse_that::parsers::json::{JsonValue, json_parser};
+    use parse_that::*;
+
+    #[test]
+    fn test_shared_parser_in_several_places() {
+        let word = regex("[a-z]+").shared();
+        let pair = word.parser().skip(string("=")).then(word.clone().parser());
+        assert_eq!(pair.parse("key=value"), Some(("key", "value")));
+        assert_eq!(pair.parse("key="), None);
+    }
+
+    #[test]
+    fn test_shared_parser_keeps_flags() {
+        let number = regex(r"\d+").trim_whitespace().shared();
+        let sum = number.parser().sep_by(string("+"), 1..);
+        assert_eq!(sum.parse(" 1 + 2 +3"), Some(vec!["1", "2", "3"]));
+    }
+
+    #[test]
+    fn test_recursive_nesting() {
+        // expr = "(" expr ")" | digit
+        let expr = recursive(|expr| {
+            expr.parser()
+                .wrap(string("("), string(")"))
+                .map(|n: u32| n + 1)
+                | regex("[0-9]").map(|_| 0)
+        });
+        assert_eq!(expr.parse("7"), Some(0));
+        assert_eq!(expr.parse("(((7)))"), Some(3));
+        assert_eq!(expr.parse("((7)"), None);
+    }
+
+    #[test]
+    fn test_recursive_handles_in_several_places() {
+        // pair = "<" pair "," pair ">" | letter; counts the letters
+        let pair = recursive(|pair| {
+            pair.parser()
+                .skip(string(","))
+                .then(pair.parser())
+                .wrap(string("<"), string(">"))
+                .map(|(l, r): (usize, usize)| l + r)
+                | regex("[a-z]").map(|_| 1)
+        });
+        assert_eq!(pair.parse("<a,<b,c>>"), Some(3));
+        assert_eq!(pair.parse("<<a,b>,<c,d>>"), Some(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "recursive: handle outlived its parser")]
+    fn test_handle_outliving_its_parser_panics() {
+        let mut escaped = None;
+        let digit = recursive(|digit| {
+            escaped = Some(digit.clone());
+            regex("[0-9]")
+        });
+        assert_eq!(digit.parse("7"), Some("7"));
+        drop(digit);
+        escaped.unwrap().parser().parse("7");
+    }
+
+    #[test]
+    fn test_deeply_nested_json() {
+        let depth = 64;
+        let src = format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+        let mut value = json_parser().parse(&src).unwrap();
+        let mut levels = 0;
+        while let JsonValue::Array(mut items) = value {
+            value = items.pop().unwrap();
+            levels += 1;
+        }
+        assert_eq!(levels, depth);
+        assert_eq!(value, JsonValue::Number(1.0));
+    }
+
+    #[test]
+    fn test_nested_css_rules_and_selectors() {
+        let src = "@media screen { @supports (display: grid) { a:not(.b > c, :is(d)) {} } }";
+        let nodes = css_parser().parse(src).unwrap();
+        let CssNode::AtMedia { body, .. } = &nodes[0] else {
+            panic!("expected @media, got {:?}", nodes[0]);
+        };
+        let CssNode::AtSupports { body, .. } = &body[0] else {
+            panic!("expected @supports, got {:?}", body[0]);
+        };
+        let CssNode::QualifiedRule { selector_list, .. } = &body[0] else {
+            panic!("expected a rule, got {:?}", body[0]);
+        };
+        let CssSelector::Compound(parts) = &selector_list[0] else {
+            panic!("expected a compound selector, got {:?}", selector_list[0]);
+        };
+        let CssSelector::PseudoFunction { args, .. } = &parts[1] else {
+            panic!("expected :not(), got {:?}", parts[1]);
+        };
+        assert_eq!(args.len(), 2);
+        assert!(matches!(args[0], CssSelector::Complex { .. }));
+    }
+}
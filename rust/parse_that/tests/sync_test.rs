@@ -0,0 +1,152 @@
+#[cfg(feature = "sync")]
+mod tests {
+    use parse_that::parsers::css::CssGrammar;
+    use parse_that::parsers::json::{JsonGrammar, json_parser};
+    use parse_that::parsers::json5::json5_document_parser;
+    use parse_that::*;
+    use std::sync::LazyLock;
+
+    static JSON: LazyLock<SyncParser<JsonGrammar>> = LazyLock::new(SyncParser::new);
+    static CSS: LazyLock<SyncParser<CssGrammar>> = LazyLock::new(SyncParser::new);
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    fn number<'a>() -> Parser<'a, i64> {
+        regex(r"\d+").trim_whitespace().map(|s| s.parse().unwrap())
+    }
+
+    /// expr = expr "-" term | term
+    /// term = "(" expr ")" | number
+    fn subtraction<'a>() -> Parser<'a, i64> {
+        left_recursive(|expr: Rule<'a, i64>| {
+            let inner = expr.clone();
+            let term = move || {
+                let inner = inner.clone();
+                number()
+                    | lazy(move || {
+                        inner
+                            .parser()
+                            .wrap(string("(").trim_whitespace(), string(")"))
+                    })
+            };
+            expr.parser()
+                .skip(string("-").trim_whitespace())
+                .then(term().memoize())
+                .map(|(lhs, rhs)| lhs - rhs)
+                | term()
+        })
+    }
+
+    #[test]
+    fn parsers_are_send_and_sync() {
+        assert_send_sync::<Parser<'static, i64>>();
+        assert_send_sync::<SyncParser<JsonGrammar>>();
+    }
+
+    #[test]
+    fn static_json_parser_on_owned_inputs() {
+        std::thread::scope(|s| {
+            for i in 0..8 {
+                s.spawn(move || {
+                    for j in 0..50 {
+                        let src = format!(r#"{{"thread": {i}, "items": [{j}, "x", null]}}"#);
+                        let value = JSON.parse(&src).unwrap();
+                        assert_eq!(Some(value), json_parser().parse(&src));
+                    }
+                });
+            }
+        });
+        assert!(JSON.parse_or_error("[1, ").is_err());
+    }
+
+    #[test]
+    fn static_css_parser() {
+        let results: Vec<usize> = std::thread::scope(|s| {
+            let handles: Vec<_> = (1..=4)
+                .map(|n| {
+                    s.spawn(move || {
+                        let src = ".a { color: red; }\n".repeat(n);
+                        CSS.parse(&src).unwrap().len()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(results, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn parser_shared_across_scoped_threads() {
+        let docs: Vec<String> = (0..8).map(|i| format!("[{i}, {{\"k\": {i}}}]")).collect();
+        let json = json_parser();
+        std::thread::scope(|s| {
+            for doc in &docs {
+                let json = &json;
+                s.spawn(move || assert!(json.parse(doc).is_some()));
+            }
+        });
+    }
+
+    #[test]
+    fn left_recursion_and_memo_are_per_thread() {
+        let docs: Vec<String> = (0..8).map(|i| format!("{i} - (10 - 4) - 1")).collect();
+        let parser = subtraction();
+        std::thread::scope(|s| {
+            for (i, src) in docs.iter().enumerate() {
+                let parser = &parser;
+                s.spawn(move || {
+                    for _ in 0..100 {
+                        assert_eq!(parser.parse(src), Some(i as i64 - 7));
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn memo_tables_are_per_parse() {
+        use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+        fn counted_word<'a>(calls: &'a AtomicUsize) -> Parser<'a, &'a str> {
+            let word = regex("[a-z]+");
+            Parser::new(move |state: &mut ParserState<'a>| {
+                calls.fetch_add(1, Relaxed);
+                word.call(state)
+            })
+        }
+
+        let calls = AtomicUsize::new(0);
+        let word = counted_word(&calls).memoize().shared();
+        let parser = word.parser().skip(string("!")) | word.parser().skip(string("?"));
+        std::thread::scope(|s| {
+            for _ in 0..8 {
+                let parser = &parser;
+                s.spawn(move || {
+                    for _ in 0..100 {
+                        assert_eq!(parser.parse("abc?"), Some("abc"));
+                    }
+                });
+            }
+        });
+        // One call per parse: the second branch hits that parse's own table
+        assert_eq!(calls.load(Relaxed), 800);
+    }
+
+    #[test]
+    fn json5_comments_stay_per_parse() {
+        let docs: Vec<String> = (0..4)
+            .map(|i| format!("// thread {i}\n{{a: {i}}}"))
+            .collect();
+        let parser = json5_document_parser();
+        std::thread::scope(|s| {
+            for (i, src) in docs.iter().enumerate() {
+                let parser = &parser;
+                s.spawn(move || {
+                    let doc = parser.parse(src).unwrap();
+                    let comments: Vec<&str> = doc.comments.iter().map(|c| c.as_str()).collect();
+                    assert_eq!(comments, [format!("// thread {i}")]);
+                });
+            }
+        });
+    }
+}
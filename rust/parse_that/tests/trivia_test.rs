@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use parse_that::parsers::json::json_parser;
+    use parse_that::*;
+    use std::sync::Arc;
+
+    fn c_like() -> Trivia {
+        Trivia::default()
+            .line_comment("//")
+            .block_comment("/*", "*/")
+    }
+
+    fn words<'a>() -> Parser<'a, Vec<&'a str>> {
+        regex("[a-z]+").trim_whitespace().many(..)
+    }
+
+    #[test]
+    fn test_default_trivia_is_ascii_whitespace() {
+        let (trivia, state) = (Trivia::default(), ParserState::new(" \t\r\n\x0Cx"));
+        assert_eq!(trivia.skip(state.src, 0), 4);
+        assert_eq!(trim_leading_whitespace(&state), 4);
+    }
+
+    #[test]
+    fn test_trim_whitespace_skips_comments() {
+        let src = "one // two\n /* three */ four/**/five //";
+        assert_eq!(words().parse(src), Some(vec!["one"]));
+        let parser = words().trivia(c_like());
+        assert_eq!(parser.parse(src), Some(vec!["one", "four", "five"]));
+    }
+
+    #[test]
+    fn test_unterminated_block_comment_is_not_trivia() {
+        let parser = words().skip(string("/*")).trivia(c_like());
+        let (value, state) = parser.parse_return_state("a b /* c");
+        assert_eq!(value, Some(vec!["a", "b"]));
+        assert_eq!(state.offset, 6);
+    }
+
+    #[test]
+    fn test_custom_bytes_and_unicode_whitespace() {
+        let src = "a\x0Cb\u{00A0}c\u{3000}\u{2028}d";
+        let form_feed = Trivia::new(b" \x0C");
+        assert_eq!(
+            words().trivia(form_feed.clone()).parse(src),
+            Some(vec!["a", "b"])
+        );
+        let parser = words().trivia(form_feed.unicode_whitespace());
+        assert_eq!(parser.parse(src), Some(vec!["a", "b", "c", "d"]));
+    }
+
+    #[test]
+    fn test_long_runs_take_the_simd_path() {
+        for n in [1, 15, 16, 17, 33, 100] {
+            let gap = " \x0C\t".repeat(n);
+            let src = format!("a{gap}b{gap}#c\n{gap}");
+            let parser = words().trivia(Trivia::new(b" \t\n\x0C").line_comment("#"));
+            let (value, state) = parser.parse_return_state(&src);
+            assert_eq!(value, Some(vec!["a", "b"]), "n = {n}");
+            assert_eq!(state.offset, src.len(), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_sep_by_ws_and_span_parsers() {
+        let src = "1, # one\n 2 #\n, 3";
+        let trivia = || Trivia::default().line_comment("#");
+        let list = regex(r"\d+").sep_by_ws(string(","), ..).trivia(trivia());
+        assert_eq!(list.parse(src), Some(vec!["1", "2", "3"]));
+        let list = sp_regex(r"\d+")
+            .sep_by_ws_span(sp_string(","), ..)
+            .into_parser()
+            .trivia(trivia());
+        assert_eq!(list.parse(src).map(|span| span.as_str()), Some(src));
+    }
+
+    #[test]
+    fn test_json_with_comments() {
+        let plain = r#"{"a": [1, 2], "b": {"c": null}}"#;
+        let commented = r#"// config
+            {
+                "a": [1, /* two */ 2], // trailing
+                "b": {"c": null}
+            }
+            /* end */"#;
+        assert_eq!(json_parser().parse(commented), None);
+        let parsed = json_parser().trivia(c_like()).parse(commented);
+        assert!(parsed.is_some());
+        assert_eq!(parsed, json_parser().parse(plain));
+    }
+
+    #[test]
+    fn test_trivia_is_scoped_to_the_parser() {
+        let hash = regex("[a-z]+")
+            .trim_whitespace()
+            .many(..)
+            .trivia(Trivia::default().line_comment("#"));
+        let parser = hash.then(words()).trivia(c_like());
+        let (value, state) = parser.parse_return_state("a #x\n b // y\n c #z");
+        assert_eq!(value, Some((vec!["a", "b"], vec!["c"])));
+        assert_eq!(&state.src[state.offset..], "#z");
+        assert_eq!(state.trivia, None);
+    }
+
+    #[test]
+    fn test_trivia_on_the_state() {
+        let mut state = ParserState::new("a /* b */ c");
+        state.trivia = Some(Arc::new(c_like()));
+        assert_eq!(words().call(&mut state), Some(vec!["a", "c"]));
+    }
+}